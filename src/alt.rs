@@ -0,0 +1,86 @@
+use crate::{InputDebug, Recoverable, Step};
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct AltError<'a, ChildErrors> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// Every branch's error, in order
+    pub child_errors: ChildErrors,
+}
+
+impl<'a, ChildErrors: Debug> Debug for AltError<'a, ChildErrors> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("AltError")
+            .field("at", &InputDebug(self.at))
+            .field("child_errors", &self.child_errors)
+            .finish()
+    }
+}
+
+impl<'a, ChildErrors> Display for AltError<'a, ChildErrors> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "no branch matched")
+    }
+}
+
+/// A tuple of parsers that are tried in order. Implemented for tuples of 2 to 12 parsers.
+pub trait Alt<'a, Output, Error> {
+    fn parse_alt(&self, input: &'a [u8]) -> Step<'a, Output, Error>;
+}
+
+macro_rules! alt_impl {
+    ($($parser:ident $error:ident $index:tt),+) => {
+        impl<'a, Output, Error, $($parser, $error),+> Alt<'a, Output, Error> for ($($parser,)+)
+        where
+            $($parser: Fn(&'a [u8]) -> Step<'a, Output, $error>,)+
+            $($error: Recoverable,)+
+            Error: From<AltError<'a, ($($error,)+)>> $(+ From<$error>)+,
+        {
+            #[allow(non_snake_case)]
+            fn parse_alt(&self, input: &'a [u8]) -> Step<'a, Output, Error> {
+                $(
+                    let $error = match (self.$index)(input) {
+                        (rest, Ok(x)) => return (rest, Ok(x)),
+                        (_, Err(e)) if !e.is_recoverable() => return (input, Err(e.into())),
+                        (_, Err(e)) => e,
+                    };
+                )+
+                let error = AltError {
+                    at: input,
+                    child_errors: ($($error,)+),
+                };
+                (input, Err(error.into()))
+            }
+        }
+    };
+}
+
+alt_impl!(A EA 0, B EB 1);
+alt_impl!(A EA 0, B EB 1, C EC 2);
+alt_impl!(A EA 0, B EB 1, C EC 2, D ED 3);
+alt_impl!(A EA 0, B EB 1, C EC 2, D ED 3, E EE 4);
+alt_impl!(A EA 0, B EB 1, C EC 2, D ED 3, E EE 4, F EF 5);
+alt_impl!(A EA 0, B EB 1, C EC 2, D ED 3, E EE 4, F EF 5, G EG 6);
+alt_impl!(A EA 0, B EB 1, C EC 2, D ED 3, E EE 4, F EF 5, G EG 6, H EH 7);
+alt_impl!(A EA 0, B EB 1, C EC 2, D ED 3, E EE 4, F EF 5, G EG 6, H EH 7, I EI 8);
+alt_impl!(A EA 0, B EB 1, C EC 2, D ED 3, E EE 4, F EF 5, G EG 6, H EH 7, I EI 8, J EJ 9);
+alt_impl!(
+    A EA 0, B EB 1, C EC 2, D ED 3, E EE 4, F EF 5, G EG 6, H EH 7, I EI 8, J EJ 9, K EK 10
+);
+alt_impl!(
+    A EA 0, B EB 1, C EC 2, D ED 3, E EE 4, F EF 5, G EG 6, H EH 7, I EI 8, J EJ 9, K EK 10,
+    L EL 11
+);
+
+/// Try a tuple of parsers in order, outputting the first success. Fails with every branch's
+/// error if none succeed, or with a branch's error as soon as it isn't [`Recoverable`].
+pub fn alt<'a, Output, Error>(
+    parsers: impl Alt<'a, Output, Error>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| parsers.parse_alt(input)
+}
+
+std_error! {
+    AltError<'a, ChildErrors>;
+}
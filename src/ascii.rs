@@ -0,0 +1,158 @@
+use crate::{satisfy, tag, take_till_byte, take_while, Terminator};
+use crate::{ByteError, InputDebug, SatisfyError, Step, TagError, TakeTillError, TakeWhileError};
+use core::fmt::{self, Debug, Display, Formatter};
+
+/// Take one or more bytes that match a predicate, failing on the first like [`satisfy`].
+fn satisfy1<'a, Error: From<ByteError<'a>> + From<SatisfyError<'a>>>(
+    predicate: fn(u8) -> bool,
+    input: &'a [u8],
+) -> Step<'a, &'a [u8], Error> {
+    let (rest, _) = step!(input, satisfy::<Error>(predicate)(input));
+    let (rest, _) = take_while(predicate)(rest);
+    let len = input.len() - rest.len();
+    (rest, Ok(&input[..len]))
+}
+
+/// Take one or more ASCII digits, `0` to `9`.
+pub fn ascii_digit1<'a, Error: From<ByteError<'a>> + From<SatisfyError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, &'a [u8], Error> {
+    satisfy1(|b| b.is_ascii_digit(), input)
+}
+
+/// Take one or more ASCII letters, `a` to `z` and `A` to `Z`.
+pub fn ascii_alpha1<'a, Error: From<ByteError<'a>> + From<SatisfyError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, &'a [u8], Error> {
+    satisfy1(|b| b.is_ascii_alphabetic(), input)
+}
+
+fn is_space(b: u8) -> bool {
+    b == b' ' || b == b'\t'
+}
+
+/// Take zero or more spaces and tabs.
+pub fn space0(input: &[u8]) -> Step<'_, &[u8], TakeWhileError> {
+    take_while(is_space)(input)
+}
+
+/// Take one or more spaces and tabs.
+pub fn space1<'a, Error: From<ByteError<'a>> + From<SatisfyError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, &'a [u8], Error> {
+    satisfy1(is_space, input)
+}
+
+/// Take a line up to `\n` or `\r\n`, consuming the line ending but not outputting it. Fails if
+/// there's no `\n`, so a last line without one is left in the input.
+pub fn line<'a, Error: From<TakeTillError<'a>>>(input: &'a [u8]) -> Step<'a, &'a [u8], Error> {
+    let (rest, out) = step!(
+        input,
+        take_till_byte::<TakeTillError>(b'\n', Terminator::Consume)(input)
+    );
+    (rest, Ok(out.strip_suffix(b"\r").unwrap_or(out)))
+}
+
+/// Match a `\r\n` line ending.
+pub fn crlf<'a, Error: From<TagError<'a>>>(input: &'a [u8]) -> Step<'a, &'a [u8], Error> {
+    tag(b"\r\n")(input)
+}
+
+/// Match a `\n` line ending.
+pub fn newline<'a, Error: From<TagError<'a>>>(input: &'a [u8]) -> Step<'a, &'a [u8], Error> {
+    tag(b"\n")(input)
+}
+
+#[derive(Clone, PartialEq)]
+pub struct AsciiNumError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The base of the number
+    pub radix: u32,
+}
+
+impl<'a> Debug for AsciiNumError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("AsciiNumError")
+            .field("at", &InputDebug(self.at))
+            .field("radix", &self.radix)
+            .finish()
+    }
+}
+
+impl<'a> Display for AsciiNumError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "expected a base {} number that fits in 64 bits",
+            self.radix
+        )
+    }
+}
+
+/// Parse the longest run of digits in a radix, failing if there are none or they overflow.
+fn ascii_num<'a, Error: From<AsciiNumError<'a>>>(
+    radix: u32,
+    input: &'a [u8],
+) -> Step<'a, u64, Error> {
+    let mut value: u64 = 0;
+    let mut len = 0;
+    for &b in input {
+        let digit = match (b as char).to_digit(radix) {
+            Some(x) => x,
+            None => break,
+        };
+        value = match value
+            .checked_mul(radix.into())
+            .and_then(|x| x.checked_add(digit.into()))
+        {
+            Some(x) => x,
+            None => return (input, Err(AsciiNumError { at: input, radix }.into())),
+        };
+        len += 1;
+    }
+    match len {
+        0 => (input, Err(AsciiNumError { at: input, radix }.into())),
+        _ => (&input[len..], Ok(value)),
+    }
+}
+
+/// Parse ASCII decimal digits into a `u64`, like the numbers in WARC or HTTP headers.
+pub fn decimal_u64<'a, Error: From<AsciiNumError<'a>>>(input: &'a [u8]) -> Step<'a, u64, Error> {
+    ascii_num(10, input)
+}
+
+/// Parse ASCII octal digits into a `u64`, like the sizes in tar headers. Padding around the
+/// digits isn't skipped, so combine it with [`space0`] or a [`take`](crate::take) of the field.
+pub fn octal_u64<'a, Error: From<AsciiNumError<'a>>>(input: &'a [u8]) -> Step<'a, u64, Error> {
+    ascii_num(8, input)
+}
+
+/// Parse ASCII hex digits, in either case, into a `u64`, like HTTP chunk sizes.
+pub fn hex_u64<'a, Error: From<AsciiNumError<'a>>>(input: &'a [u8]) -> Step<'a, u64, Error> {
+    ascii_num(16, input)
+}
+
+std_error! {
+    AsciiNumError<'a>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ErrorKind, ParseError};
+
+    #[test]
+    fn digit1_errors() {
+        let input = b"12a";
+        assert_eq!(
+            ascii_digit1::<ParseError>(input),
+            (&input[2..], Ok(&input[..2]))
+        );
+        let (rest, x) = ascii_digit1::<ParseError>(&input[2..]);
+        assert_eq!(rest, b"a");
+        assert_eq!(x.unwrap_err().kind, ErrorKind::Satisfy);
+        let (_, x) = ascii_digit1::<ParseError>(&input[3..]);
+        assert_eq!(x.unwrap_err().kind, ErrorKind::Byte);
+    }
+}
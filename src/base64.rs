@@ -0,0 +1,107 @@
+use crate::{take, InputDebug, Step, TakeError};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct Base64Error<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// Offset of the invalid character from where the error happened
+    pub offset: usize,
+}
+
+impl<'a> Debug for Base64Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Base64Error")
+            .field("at", &InputDebug(self.at))
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+impl<'a> Display for Base64Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid base64 at offset {}", self.offset)
+    }
+}
+
+impl<'a> From<TakeError<'a>> for Base64Error<'a> {
+    fn from(x: TakeError<'a>) -> Self {
+        Self { at: x.0, offset: 0 }
+    }
+}
+
+fn sextet(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Decode base64 text, with optional padding, or output the offset of the invalid character.
+fn decode(text: &[u8]) -> Result<Vec<u8>, usize> {
+    let padding = text
+        .iter()
+        .rev()
+        .take(2)
+        .take_while(|&&c| c == b'=')
+        .count();
+    let (data, _) = text.split_at(text.len() - padding);
+    if padding != 0 && text.len() % 4 != 0 {
+        return Err(data.len());
+    }
+    let mut out = Vec::with_capacity(data.len() / 4 * 3 + 2);
+    for (i, chunk) in data.chunks(4).enumerate() {
+        let mut bits = 0u32;
+        for (j, &c) in chunk.iter().enumerate() {
+            match sextet(c) {
+                Some(x) => bits |= (x as u32) << (18 - 6 * j),
+                None => return Err(i * 4 + j),
+            }
+        }
+        let bytes = bits.to_be_bytes();
+        match chunk.len() {
+            4 => out.extend_from_slice(&bytes[1..4]),
+            3 if bits & 0xff == 0 => out.extend_from_slice(&bytes[1..3]),
+            2 if bits & 0xffff == 0 => out.push(bytes[1]),
+            // Too few characters, or the unused bits are not zero.
+            len => return Err(i * 4 + len - 1),
+        }
+    }
+    Ok(out)
+}
+
+/// Take N characters of base64 text, outputting the decoded bytes.
+pub fn base64<'a, Error: From<Base64Error<'a>>>(
+    count: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<u8>, Error> {
+    move |input| match take::<Base64Error>(count)(input) {
+        (rest, Ok(text)) => match decode(text) {
+            Ok(x) => (rest, Ok(x)),
+            Err(offset) => (input, Err(Base64Error { at: input, offset }.into())),
+        },
+        (_, Err(e)) => (input, Err(e.into())),
+    }
+}
+
+/// Take base64 text until a delimiter or the end of the input, outputting the decoded bytes. The
+/// delimiter is not consumed.
+pub fn base64_until<'a, Error: From<Base64Error<'a>>>(
+    delimiter: u8,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<u8>, Error> {
+    move |input| {
+        let count = input
+            .iter()
+            .position(|&b| b == delimiter)
+            .unwrap_or(input.len());
+        base64(count)(input)
+    }
+}
+
+std_error! {
+    Base64Error<'a>;
+}
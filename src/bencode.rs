@@ -0,0 +1,139 @@
+//! Bencode parsers, as used by torrent files.
+
+use crate::{InputDebug, Step};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::str;
+
+/// How deep lists and dictionaries may be nested by default.
+const MAX_DEPTH: usize = 64;
+
+#[derive(Clone, PartialEq)]
+pub struct BencodeError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for BencodeError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("BencodeError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for BencodeError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid bencode")
+    }
+}
+
+/// A bencode value, borrowing from the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value<'a> {
+    Integer(i64),
+    Bytes(&'a [u8]),
+    List(Vec<Value<'a>>),
+    /// Entries in the order they appear, which is sorted by key
+    Dict(Vec<(&'a [u8], Value<'a>)>),
+}
+
+type Parsed<'a, T> = Result<(&'a [u8], T), &'a [u8]>;
+
+/// Split off digits that have no leading zeros.
+fn digits(input: &[u8]) -> Parsed<'_, &str> {
+    let len = input
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .unwrap_or(input.len());
+    match &input[..len] {
+        [] => Err(input),
+        [b'0', _, ..] => Err(input),
+        // Only ASCII digits were accepted above.
+        x => Ok((&input[len..], str::from_utf8(x).map_err(|_| input)?)),
+    }
+}
+
+fn integer(input: &[u8]) -> Parsed<'_, i64> {
+    let body = input.strip_prefix(b"i").ok_or(input)?;
+    let (negative, unsigned) = match body.strip_prefix(b"-") {
+        Some(x) => (true, x),
+        None => (false, body),
+    };
+    let (rest, text) = digits(unsigned)?;
+    let rest = rest.strip_prefix(b"e").ok_or(rest)?;
+    let value = match negative {
+        // `-0` is not allowed
+        true if text == "0" => return Err(body),
+        true => text.parse::<i128>().map(|x| -x),
+        false => text.parse::<i128>(),
+    };
+    match value.ok().and_then(|x| i64::try_from(x).ok()) {
+        Some(x) => Ok((rest, x)),
+        None => Err(input),
+    }
+}
+
+fn bytes(input: &[u8]) -> Parsed<'_, &[u8]> {
+    let (rest, len) = digits(input)?;
+    let len = len.parse::<usize>().map_err(|_| input)?;
+    let rest = rest.strip_prefix(b":").ok_or(rest)?;
+    match rest.get(..len) {
+        Some(x) => Ok((&rest[len..], x)),
+        None => Err(input),
+    }
+}
+
+fn value_at(input: &[u8], depth: usize) -> Parsed<'_, Value<'_>> {
+    match input.first() {
+        Some(b'i') => integer(input).map(|(rest, x)| (rest, Value::Integer(x))),
+        Some(b'0'..=b'9') => bytes(input).map(|(rest, x)| (rest, Value::Bytes(x))),
+        Some(b'l') if depth > 0 => {
+            let mut rest = &input[1..];
+            let mut items = Vec::new();
+            while rest.first() != Some(&b'e') {
+                let (next, item) = value_at(rest, depth - 1)?;
+                items.push(item);
+                rest = next;
+            }
+            Ok((&rest[1..], Value::List(items)))
+        }
+        Some(b'd') if depth > 0 => {
+            let mut rest = &input[1..];
+            let mut entries: Vec<(&[u8], Value)> = Vec::new();
+            while rest.first() != Some(&b'e') {
+                let (next, key) = bytes(rest)?;
+                // Keys must be sorted, without duplicates
+                if let Some((last, _)) = entries.last() {
+                    if *last >= key {
+                        return Err(rest);
+                    }
+                }
+                let (next, item) = value_at(next, depth - 1)?;
+                entries.push((key, item));
+                rest = next;
+            }
+            Ok((&rest[1..], Value::Dict(entries)))
+        }
+        _ => Err(input),
+    }
+}
+
+/// Parse a bencode value, with lists and dictionaries nested up to a given depth.
+pub fn value_with_depth<'a, Error: From<BencodeError<'a>>>(
+    max_depth: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, Value<'a>, Error> {
+    move |input| match value_at(input, max_depth) {
+        Ok((rest, x)) => (rest, Ok(x)),
+        Err(at) => (input, Err(BencodeError(at).into())),
+    }
+}
+
+/// Parse a bencode value, with lists and dictionaries nested up to 64 deep.
+pub fn value<'a, Error: From<BencodeError<'a>>>(input: &'a [u8]) -> Step<'a, Value<'a>, Error> {
+    value_with_depth(MAX_DEPTH)(input)
+}
+
+std_error! {
+    BencodeError<'a>;
+}
@@ -0,0 +1,216 @@
+//! Bitcoin block header and transaction parsers.
+
+use crate::{compact_size, take, CompactSizeError, InputDebug, Step, TakeError};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct BitcoinError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for BitcoinError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("BitcoinError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for BitcoinError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid bitcoin data")
+    }
+}
+
+impl<'a> From<TakeError<'a>> for BitcoinError<'a> {
+    fn from(x: TakeError<'a>) -> Self {
+        Self(x.0)
+    }
+}
+
+impl<'a> From<CompactSizeError<'a>> for BitcoinError<'a> {
+    fn from(x: CompactSizeError<'a>) -> Self {
+        Self(x.0)
+    }
+}
+
+fn hash<'a>(input: &'a [u8]) -> Step<'a, &'a [u8; 32], BitcoinError<'a>> {
+    let (rest, x) = step!(input, take::<BitcoinError>(32)(input));
+    // `take` only succeeds with exactly 32 bytes.
+    (rest, Ok(x.try_into().unwrap()))
+}
+
+fn uint32<'a>(input: &'a [u8]) -> Step<'a, u32, BitcoinError<'a>> {
+    let (rest, x) = step!(input, take::<BitcoinError>(4)(input));
+    (rest, Ok(u32::from_le_bytes([x[0], x[1], x[2], x[3]])))
+}
+
+fn uint64<'a>(input: &'a [u8]) -> Step<'a, u64, BitcoinError<'a>> {
+    let (rest, x) = step!(input, take::<BitcoinError>(8)(input));
+    let mut bytes = [0; 8];
+    bytes.copy_from_slice(x);
+    (rest, Ok(u64::from_le_bytes(bytes)))
+}
+
+/// Take a count, rejecting it if there aren't at least that many bytes left.
+fn count<'a>(input: &'a [u8]) -> Step<'a, usize, BitcoinError<'a>> {
+    let (rest, n) = step!(input, compact_size::<BitcoinError>(input));
+    match usize::try_from(n) {
+        Ok(n) if n <= rest.len() => (rest, Ok(n)),
+        _ => (input, Err(BitcoinError(input))),
+    }
+}
+
+/// Take bytes prefixed with their CompactSize length.
+fn var_bytes<'a>(input: &'a [u8]) -> Step<'a, &'a [u8], BitcoinError<'a>> {
+    let (rest, len) = step!(input, count(input));
+    let (rest, x) = step!(input, take::<BitcoinError>(len)(rest));
+    (rest, Ok(x))
+}
+
+/// An 80 byte block header. Hashes are in their serialized byte order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockHeader<'a> {
+    pub version: i32,
+    pub prev_block: &'a [u8; 32],
+    pub merkle_root: &'a [u8; 32],
+    /// Seconds since the Unix epoch
+    pub time: u32,
+    /// The compact encoding of the target
+    pub bits: u32,
+    pub nonce: u32,
+}
+
+/// Parse a block header.
+pub fn block_header<'a, Error: From<BitcoinError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, BlockHeader<'a>, Error> {
+    let (rest, version) = step!(input, uint32(input));
+    let (rest, prev_block) = step!(input, hash(rest));
+    let (rest, merkle_root) = step!(input, hash(rest));
+    let (rest, time) = step!(input, uint32(rest));
+    let (rest, bits) = step!(input, uint32(rest));
+    let (rest, nonce) = step!(input, uint32(rest));
+    let header = BlockHeader {
+        version: version as i32,
+        prev_block,
+        merkle_root,
+        time,
+        bits,
+        nonce,
+    };
+    (rest, Ok(header))
+}
+
+/// A transaction input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TxIn<'a> {
+    /// The transaction ID of the output being spent
+    pub prev_txid: &'a [u8; 32],
+    /// The index of the output being spent
+    pub prev_index: u32,
+    pub script_sig: &'a [u8],
+    pub sequence: u32,
+}
+
+/// A transaction output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TxOut<'a> {
+    /// The amount in satoshis
+    pub value: u64,
+    pub script_pubkey: &'a [u8],
+}
+
+/// A transaction, borrowing its scripts and witnesses from the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transaction<'a> {
+    pub version: i32,
+    pub inputs: Vec<TxIn<'a>>,
+    pub outputs: Vec<TxOut<'a>>,
+    /// The witness stack of each input, or empty without segregated witness data
+    pub witnesses: Vec<Vec<&'a [u8]>>,
+    pub lock_time: u32,
+}
+
+fn tx_in<'a>(input: &'a [u8]) -> Step<'a, TxIn<'a>, BitcoinError<'a>> {
+    let (rest, prev_txid) = step!(input, hash(input));
+    let (rest, prev_index) = step!(input, uint32(rest));
+    let (rest, script_sig) = step!(input, var_bytes(rest));
+    let (rest, sequence) = step!(input, uint32(rest));
+    let x = TxIn {
+        prev_txid,
+        prev_index,
+        script_sig,
+        sequence,
+    };
+    (rest, Ok(x))
+}
+
+fn tx_out<'a>(input: &'a [u8]) -> Step<'a, TxOut<'a>, BitcoinError<'a>> {
+    let (rest, value) = step!(input, uint64(input));
+    let (rest, script_pubkey) = step!(input, var_bytes(rest));
+    (
+        rest,
+        Ok(TxOut {
+            value,
+            script_pubkey,
+        }),
+    )
+}
+
+/// Run a parser for each of a CompactSize count of items.
+fn list<'a, T>(
+    input: &'a [u8],
+    item: impl Fn(&'a [u8]) -> Step<'a, T, BitcoinError<'a>>,
+) -> Step<'a, Vec<T>, BitcoinError<'a>> {
+    let (mut rest, n) = step!(input, count(input));
+    let mut items = Vec::with_capacity(n);
+    for _ in 0..n {
+        let (next, x) = step!(input, item(rest));
+        items.push(x);
+        rest = next;
+    }
+    (rest, Ok(items))
+}
+
+/// Parse a transaction, with or without segregated witness data.
+pub fn transaction<'a, Error: From<BitcoinError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, Transaction<'a>, Error> {
+    let (rest, version) = step!(input, uint32(input));
+    // A zero input count is the segregated witness marker, followed by a flag of 1
+    let (rest, segwit) = match rest {
+        [0, 1, rest @ ..] => (rest, true),
+        _ => (rest, false),
+    };
+    let (rest, inputs) = step!(input, list(rest, tx_in));
+    let (rest, outputs) = step!(input, list(rest, tx_out));
+    let (rest, witnesses) = match segwit {
+        true => {
+            let mut rest = rest;
+            let mut witnesses = Vec::with_capacity(inputs.len());
+            for _ in 0..inputs.len() {
+                let (next, stack) = step!(input, list(rest, var_bytes));
+                witnesses.push(stack);
+                rest = next;
+            }
+            (rest, witnesses)
+        }
+        false => (rest, Vec::new()),
+    };
+    let (rest, lock_time) = step!(input, uint32(rest));
+    let tx = Transaction {
+        version: version as i32,
+        inputs,
+        outputs,
+        witnesses,
+        lock_time,
+    };
+    (rest, Ok(tx))
+}
+
+std_error! {
+    BitcoinError<'a>;
+}
@@ -0,0 +1,58 @@
+use crate::{OptError, Step};
+#[cfg(feature = "encoding_rs")]
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+
+/// The encoding given by a byte order mark.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Bom {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Utf32Le,
+    Utf32Be,
+}
+
+impl Bom {
+    /// The encoding to decode the following text with, or `None` for UTF-32, which `encoding_rs`
+    /// doesn't support. **Requires the `encoding_rs` feature**
+    #[cfg(feature = "encoding_rs")]
+    pub fn encoding(self) -> Option<&'static Encoding> {
+        match self {
+            Bom::Utf8 => Some(UTF_8),
+            Bom::Utf16Le => Some(UTF_16LE),
+            Bom::Utf16Be => Some(UTF_16BE),
+            Bom::Utf32Le | Bom::Utf32Be => None,
+        }
+    }
+}
+
+/// Detect and skip a UTF-8, UTF-16, or UTF-32 byte order mark, outputting `None` if there isn't
+/// one.
+pub fn bom<'a>(input: &'a [u8]) -> Step<'a, Option<Bom>, OptError> {
+    match input {
+        [0xef, 0xbb, 0xbf, rest @ ..] => (rest, Ok(Some(Bom::Utf8))),
+        // The UTF-32 little-endian mark starts with the UTF-16 one, so check it first
+        [0xff, 0xfe, 0x00, 0x00, rest @ ..] => (rest, Ok(Some(Bom::Utf32Le))),
+        [0x00, 0x00, 0xfe, 0xff, rest @ ..] => (rest, Ok(Some(Bom::Utf32Be))),
+        [0xff, 0xfe, rest @ ..] => (rest, Ok(Some(Bom::Utf16Le))),
+        [0xfe, 0xff, rest @ ..] => (rest, Ok(Some(Bom::Utf16Be))),
+        _ => (input, Ok(None)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utf32_before_utf16() {
+        let input = b"\xff\xfe\x00\x00a\x00\x00\x00";
+        assert_eq!(bom(input), (&input[4..], Ok(Some(Bom::Utf32Le))));
+        let input = b"\xff\xfea\x00";
+        assert_eq!(bom(input), (&input[2..], Ok(Some(Bom::Utf16Le))));
+        let input = b"\x00\x00\xfe\xff";
+        assert_eq!(bom(input), (&input[4..], Ok(Some(Bom::Utf32Be))));
+        let input = b"a";
+        assert_eq!(bom(input), (&input[..], Ok(None)));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::Step;
+use alloc::vec::Vec;
+
+/// A cursor over input split between several segments, such as the buffers handed out by a
+/// network stack, that parsers are run against without joining the segments first.
+#[derive(Clone)]
+pub struct Chain<'a, S> {
+    segments: &'a [S],
+    /// Index of the segment holding the current position
+    index: usize,
+    /// Offset of the current position in that segment
+    offset: usize,
+    /// Offset of the current position from the start of the first segment
+    position: usize,
+}
+
+impl<'a, S: AsRef<[u8]>> Chain<'a, S> {
+    pub fn new(segments: &'a [S]) -> Self {
+        let mut chain = Self {
+            segments,
+            index: 0,
+            offset: 0,
+            position: 0,
+        };
+        chain.skip_empty();
+        chain
+    }
+
+    /// Move past any used up or empty segments.
+    fn skip_empty(&mut self) {
+        while self.index < self.segments.len()
+            && self.offset == self.segments[self.index].as_ref().len()
+        {
+            self.index += 1;
+            self.offset = 0;
+        }
+    }
+
+    /// Run a parser at the current position, advancing past what it consumed.
+    ///
+    /// The parser runs on the rest of the current segment first. If it fails or consumes all of it
+    /// and more segments follow, it's run again on a copy with the next segment joined on, and so
+    /// on, so only the bytes around a boundary are copied. This way parsers that take as much as
+    /// they can, like [`take_while`](crate::take_while), aren't cut off at a boundary. Because the
+    /// input may be a copy, the output and error can't borrow it.
+    pub fn parse<Output, Error>(
+        &mut self,
+        parser: impl for<'b> Fn(&'b [u8]) -> Step<'b, Output, Error>,
+    ) -> Result<Output, Error> {
+        let chunk = self.chunk();
+        let (rest, mut result) = (parser)(chunk);
+        let mut consumed = chunk.len() - rest.len();
+        let mut exhausted = rest.is_empty();
+        let mut window = Vec::new();
+        let mut next = self.index + 1;
+        while (result.is_err() || exhausted) && next < self.segments.len() {
+            if window.is_empty() {
+                window.extend_from_slice(chunk);
+            }
+            window.extend_from_slice(self.segments[next].as_ref());
+            next += 1;
+            let (rest, x) = (parser)(&window);
+            consumed = window.len() - rest.len();
+            exhausted = rest.is_empty();
+            result = x;
+        }
+        if result.is_ok() {
+            self.advance(consumed);
+        }
+        result
+    }
+
+    /// The bytes left in the current segment.
+    pub fn chunk(&self) -> &'a [u8] {
+        match self.segments.get(self.index) {
+            Some(x) => &x.as_ref()[self.offset..],
+            None => &[],
+        }
+    }
+
+    /// Skip N bytes, crossing into later segments as needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are less than N bytes left.
+    pub fn advance(&mut self, mut count: usize) {
+        assert!(count <= self.len(), "advanced past the end of the chain");
+        self.position += count;
+        while count != 0 {
+            let len = self.chunk().len();
+            match count < len {
+                true => {
+                    self.offset += count;
+                    count = 0;
+                }
+                false => {
+                    self.index += 1;
+                    self.offset = 0;
+                    count -= len;
+                }
+            }
+        }
+        self.skip_empty();
+    }
+
+    /// Offset of the current position from the start of the first segment.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// How many bytes are left in all the segments.
+    pub fn len(&self) -> usize {
+        let later = self.segments.get(self.index + 1..).unwrap_or(&[]);
+        self.chunk().len() + later.iter().map(|x| x.as_ref().len()).sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index >= self.segments.len()
+    }
+}
+
+/// **Requires the `bytes` feature**
+#[cfg(feature = "bytes")]
+impl<'a, S: AsRef<[u8]>> bytes::Buf for Chain<'a, S> {
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        Chain::chunk(self)
+    }
+
+    fn advance(&mut self, count: usize) {
+        Chain::advance(self, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &[u8]) -> Step<'_, usize, ()> {
+        let len = input.iter().take_while(|b| b.is_ascii_digit()).count();
+        (&input[len..], Ok(len))
+    }
+
+    #[test]
+    fn parse_across_boundary() {
+        // The first segment is consumed entirely but succeeds, so it has to keep joining
+        let segments = [&b"12"[..], b"34", b"5x"];
+        let mut chain = Chain::new(&segments);
+        assert_eq!(chain.parse(digits), Ok(5));
+        assert_eq!(chain.position(), 5);
+        assert_eq!(chain.chunk(), b"x");
+    }
+}
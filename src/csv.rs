@@ -0,0 +1,151 @@
+//! CSV and other delimiter-separated value parsers.
+
+use crate::{InputDebug, Step};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct CsvError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for CsvError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("CsvError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for CsvError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid CSV")
+    }
+}
+
+/// Split off a field, outputting it with quotes removed, or where it was invalid.
+fn split_field(input: &[u8], delimiter: u8) -> Step<'_, Cow<'_, [u8]>, &[u8]> {
+    let body = match input.strip_prefix(b"\"") {
+        Some(x) => x,
+        None => {
+            let len = input
+                .iter()
+                .position(|&b| b == delimiter || b == b'\n' || b == b'\r')
+                .unwrap_or(input.len());
+            return (&input[len..], Ok(Cow::Borrowed(&input[..len])));
+        }
+    };
+    let mut out: Option<Vec<u8>> = None;
+    let mut start = 0;
+    loop {
+        let quote = match body[start..].iter().position(|&b| b == b'"') {
+            Some(x) => start + x,
+            None => return (input, Err(input)),
+        };
+        let rest = &body[quote + 1..];
+        if rest.first() == Some(&b'"') {
+            // An escaped quote
+            let out = out.get_or_insert_with(Vec::new);
+            out.extend_from_slice(&body[start..quote + 1]);
+            start = quote + 2;
+            continue;
+        }
+        match rest.first() {
+            None | Some(b'\n' | b'\r') => {}
+            Some(&b) if b == delimiter => {}
+            _ => return (input, Err(rest)),
+        }
+        let field = match out {
+            Some(mut x) => {
+                x.extend_from_slice(&body[start..quote]);
+                Cow::Owned(x)
+            }
+            None => Cow::Borrowed(&body[..quote]),
+        };
+        return (rest, Ok(field));
+    }
+}
+
+/// Parse a field, which may be quoted, without consuming the delimiter or line ending after it.
+pub fn field<'a, Error: From<CsvError<'a>>>(
+    delimiter: u8,
+) -> impl Fn(&'a [u8]) -> Step<'a, Cow<'a, [u8]>, Error> {
+    move |input| match split_field(input, delimiter) {
+        (rest, Ok(x)) => (rest, Ok(x)),
+        (_, Err(at)) => (input, Err(CsvError(at).into())),
+    }
+}
+
+/// A record, whose fields can be iterated without allocating.
+#[derive(Clone, Copy)]
+pub struct Record<'a> {
+    /// The text of the record, without the line ending
+    pub text: &'a [u8],
+    delimiter: u8,
+}
+
+impl<'a> Record<'a> {
+    /// Iterate the fields of the record, with quotes removed.
+    pub fn fields(&self) -> Fields<'a> {
+        Fields {
+            rest: Some(self.text),
+            delimiter: self.delimiter,
+        }
+    }
+}
+
+/// An iterator of the fields of a [`Record`].
+pub struct Fields<'a> {
+    rest: Option<&'a [u8]>,
+    delimiter: u8,
+}
+
+impl<'a> Iterator for Fields<'a> {
+    type Item = Cow<'a, [u8]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The record was validated when it was parsed.
+        let (rest, field) = split_field(self.rest?, self.delimiter);
+        let field = field.ok()?;
+        self.rest = rest.get(1..);
+        Some(field)
+    }
+}
+
+/// Parse a record of fields separated by a delimiter, along with its `\n` or `\r\n` line ending.
+/// The last record may end without a line ending.
+pub fn record<'a, Error: From<CsvError<'a>>>(
+    delimiter: u8,
+) -> impl Fn(&'a [u8]) -> Step<'a, Record<'a>, Error> {
+    move |input| {
+        if input.is_empty() {
+            return (input, Err(CsvError(input).into()));
+        }
+        let mut rest = input;
+        loop {
+            rest = match split_field(rest, delimiter) {
+                (rest, Ok(_)) => rest,
+                (_, Err(at)) => return (input, Err(CsvError(at).into())),
+            };
+            match rest.first() {
+                Some(&b) if b == delimiter => rest = &rest[1..],
+                _ => break,
+            }
+        }
+        let record = Record {
+            text: &input[..input.len() - rest.len()],
+            delimiter,
+        };
+        match rest {
+            [] => (rest, Ok(record)),
+            [b'\n', rest @ ..] | [b'\r', b'\n', rest @ ..] => (rest, Ok(record)),
+            _ => (input, Err(CsvError(rest).into())),
+        }
+    }
+}
+
+std_error! {
+    CsvError<'a>;
+}
@@ -0,0 +1,71 @@
+use crate::{take, InputDebug, Step, TakeError};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use core::fmt::{self, Debug, Display, Formatter};
+use encoding_rs::{DecoderResult, Encoding};
+
+#[derive(Clone, PartialEq)]
+pub struct EncodingError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// Offset of the undecodable sequence from where the error happened
+    pub offset: usize,
+}
+
+impl<'a> Debug for EncodingError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("EncodingError")
+            .field("at", &InputDebug(self.at))
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+impl<'a> Display for EncodingError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "undecodable sequence at offset {}", self.offset)
+    }
+}
+
+impl<'a> From<TakeError<'a>> for EncodingError<'a> {
+    fn from(x: TakeError<'a>) -> Self {
+        Self { at: x.0, offset: 0 }
+    }
+}
+
+/// Offset of the first undecodable sequence in `text`.
+fn malformed_offset(text: &[u8], encoding: &'static Encoding) -> usize {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let capacity = decoder
+        .max_utf8_buffer_length_without_replacement(text.len())
+        .unwrap_or(0);
+    let mut out = String::with_capacity(capacity);
+    match decoder.decode_to_string_without_replacement(text, &mut out, true) {
+        (DecoderResult::Malformed(len, after), read) => read - after as usize - len as usize,
+        (_, read) => read,
+    }
+}
+
+/// Take N bytes of text in the given encoding, outputting it decoded. The output is only copied
+/// when the text isn't already UTF-8. **Requires the `encoding_rs` feature**
+pub fn str_encoded<'a, Error: From<EncodingError<'a>>>(
+    count: usize,
+    encoding: &'static Encoding,
+) -> impl Fn(&'a [u8]) -> Step<'a, Cow<'a, str>, Error> {
+    move |input| match take::<EncodingError>(count)(input) {
+        (rest, Ok(text)) => {
+            match encoding.decode_without_bom_handling_and_without_replacement(text) {
+                Some(x) => (rest, Ok(x)),
+                None => {
+                    let offset = malformed_offset(text, encoding);
+                    (input, Err(EncodingError { at: input, offset }.into()))
+                }
+            }
+        }
+        (_, Err(e)) => (input, Err(e.into())),
+    }
+}
+
+std_error! {
+    EncodingError<'a>;
+}
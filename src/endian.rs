@@ -0,0 +1,205 @@
+use crate::{InputDebug, Step};
+use core::fmt::{self, Debug, Display, Formatter};
+
+/// A number that can be read from bytes in either byte order.
+pub trait Number: Sized {
+    /// How many bytes encode the number
+    const SIZE: usize;
+
+    /// Read from exactly [`Self::SIZE`] little-endian bytes.
+    fn from_le(bytes: &[u8]) -> Self;
+
+    /// Read from exactly [`Self::SIZE`] big-endian bytes.
+    fn from_be(bytes: &[u8]) -> Self;
+
+    /// Read from exactly [`Self::SIZE`] native-endian bytes.
+    fn from_ne(bytes: &[u8]) -> Self;
+}
+
+macro_rules! number_impl {
+    ($($num_ty:ty),*) => {
+        $(
+            impl Number for $num_ty {
+                const SIZE: usize = core::mem::size_of::<$num_ty>();
+
+                fn from_le(bytes: &[u8]) -> Self {
+                    <$num_ty>::from_le_bytes(bytes.try_into().expect("wrong number of bytes"))
+                }
+
+                fn from_be(bytes: &[u8]) -> Self {
+                    <$num_ty>::from_be_bytes(bytes.try_into().expect("wrong number of bytes"))
+                }
+
+                fn from_ne(bytes: &[u8]) -> Self {
+                    <$num_ty>::from_ne_bytes(bytes.try_into().expect("wrong number of bytes"))
+                }
+            }
+        )*
+    };
+}
+
+number_impl!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, f32, f64);
+
+/// A byte order, for writing number parsers once for either order. See [`num`].
+pub trait Endianness {
+    /// Read a number from exactly [`Number::SIZE`] bytes.
+    fn read<T: Number>(bytes: &[u8]) -> T;
+}
+
+/// Least significant byte first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LittleEndian;
+
+/// Most significant byte first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BigEndian;
+
+/// The byte order of the target platform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NativeEndian;
+
+impl Endianness for LittleEndian {
+    fn read<T: Number>(bytes: &[u8]) -> T {
+        T::from_le(bytes)
+    }
+}
+
+impl Endianness for BigEndian {
+    fn read<T: Number>(bytes: &[u8]) -> T {
+        T::from_be(bytes)
+    }
+}
+
+impl Endianness for NativeEndian {
+    fn read<T: Number>(bytes: &[u8]) -> T {
+        T::from_ne(bytes)
+    }
+}
+
+/// A byte order chosen at runtime, like from a file header. See [`num_endian`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Endian {
+    /// Least significant byte first
+    Little,
+    /// Most significant byte first
+    Big,
+}
+
+impl Endian {
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "little")]
+    pub const NATIVE: Self = Self::Little;
+    /// The byte order of the target platform.
+    #[cfg(target_endian = "big")]
+    pub const NATIVE: Self = Self::Big;
+
+    /// Read a number from exactly [`Number::SIZE`] bytes.
+    pub fn read<T: Number>(self, bytes: &[u8]) -> T {
+        match self {
+            Self::Little => T::from_le(bytes),
+            Self::Big => T::from_be(bytes),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct NumError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// How many bytes the number needed
+    pub size: usize,
+}
+
+impl<'a> Debug for NumError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("NumError")
+            .field("at", &InputDebug(self.at))
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+impl<'a> Display for NumError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "expected {} bytes for number, {} left",
+            self.size,
+            self.at.len()
+        )
+    }
+}
+
+/// Parse a number in a byte order given by a type, like `num::<u32, BigEndian, _>`.
+pub fn num<'a, T: Number, E: Endianness, Error: From<NumError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, T, Error> {
+    match input.get(..T::SIZE) {
+        Some(bytes) => (&input[T::SIZE..], Ok(E::read(bytes))),
+        None => (
+            input,
+            Err(NumError {
+                at: input,
+                size: T::SIZE,
+            }
+            .into()),
+        ),
+    }
+}
+
+/// Parse a number in a byte order chosen at runtime.
+pub fn num_endian<'a, T: Number, Error: From<NumError<'a>>>(
+    endian: Endian,
+) -> impl Fn(&'a [u8]) -> Step<'a, T, Error> {
+    move |input| match input.get(..T::SIZE) {
+        Some(bytes) => (&input[T::SIZE..], Ok(endian.read(bytes))),
+        None => (
+            input,
+            Err(NumError {
+                at: input,
+                size: T::SIZE,
+            }
+            .into()),
+        ),
+    }
+}
+
+macro_rules! endian_impl {
+    ($($(#[$m:meta])* $num_ty:ty, $fn_name:ident;)*) => {
+        $(
+            $(#[$m])*
+            pub fn $fn_name<'a, Error: From<NumError<'a>>>(
+                endian: Endian,
+            ) -> impl Fn(&'a [u8]) -> Step<'a, $num_ty, Error> {
+                num_endian(endian)
+            }
+        )*
+    };
+}
+
+endian_impl! {
+    /// Parse unsigned 16-bit integer in a byte order chosen at runtime.
+    u16, u16e;
+    /// Parse signed 16-bit integer in a byte order chosen at runtime.
+    i16, i16e;
+    /// Parse unsigned 32-bit integer in a byte order chosen at runtime.
+    u32, u32e;
+    /// Parse signed 32-bit integer in a byte order chosen at runtime.
+    i32, i32e;
+    /// Parse unsigned 64-bit integer in a byte order chosen at runtime.
+    u64, u64e;
+    /// Parse signed 64-bit integer in a byte order chosen at runtime.
+    i64, i64e;
+    /// Parse unsigned 128-bit integer in a byte order chosen at runtime.
+    u128, u128e;
+    /// Parse signed 128-bit integer in a byte order chosen at runtime.
+    i128, i128e;
+    /// Parse 32-bit float in a byte order chosen at runtime.
+    f32, f32e;
+    /// Parse 64-bit float in a byte order chosen at runtime.
+    f64, f64e;
+}
+
+std_error! {
+    NumError<'a>;
+}
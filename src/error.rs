@@ -0,0 +1,786 @@
+#[cfg(feature = "json")]
+use crate::json;
+#[cfg(feature = "digest")]
+use crate::DigestError;
+#[cfg(feature = "encoding_rs")]
+use crate::EncodingError;
+#[cfg(feature = "lz4")]
+use crate::Lz4Error;
+#[cfg(feature = "bytemuck")]
+use crate::PodError;
+#[cfg(feature = "alloc")]
+use crate::{bencode, bitcoin, csv, Base64Error};
+use crate::{http1, ini, mqtt, pcap, tls, ws, InputDebug};
+use crate::{
+    AltError, AsciiNumError, BoolError, ByteError, ByteMapError, CStrError, ChecksumError,
+    CompactSizeError, CutError, EnumError, F16BError, F16LError, F32BError, F32LError, F32NError,
+    F64BError, F64LError, F64NError, FillError, FinishError, FlagsError, HexError, I128BError,
+    I128LError, I128NError, I16BError, I16LError, I16NError, I24BError, I24LError, I32BError,
+    I32LError, I32NError, I64BError, I64LError, I64NError, Leb128Error, LengthCountError,
+    LengthDataError, LimitError, Many0Error, Many1Error, ManyMNError, ManyTillError, MapResError,
+    NotError, NumError, OptError, OrError, ParseAtError, PercentError, QuicVarintError, RestError,
+    SByteError, SatisfyError, SeparatedList0Error, SeparatedList1Error, SeparatedListTrailingError,
+    SeqError, SkipUntilError, TagError, TakeError, TakeTillError, TakeUntilError, TakeWhile1Error,
+    TakeWhileError, TimestampError, TrailingError, TupleError, U128BError, U128LError, U128NError,
+    U16BError, U16LError, U16NError, U24BError, U24LError, U32BError, U32LError, U32NError,
+    U48BError, U48LError, U64BError, U64LError, U64NError, UnstuffError, UrlError, Utf16Error,
+    Utf8Error, VarintError, VarintOverlongError, VerifyError, VlqError,
+};
+use core::fmt::{self, Debug, Display, Formatter};
+
+/// An error that every built-in error converts into, for when the details of each parser's error
+/// aren't needed. Errors from combinators like [`seq`](crate::seq) are converted from the child's error,
+/// since it says more about what went wrong.
+#[derive(Clone, PartialEq)]
+pub struct ParseError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// What went wrong
+    pub kind: ErrorKind,
+    /// Labels added by [`context`](crate::context)
+    pub context: Context,
+    /// Whether the error came from [`cut`](crate::cut)
+    pub cut: bool,
+}
+
+impl<'a> ParseError<'a> {
+    pub fn new(at: &'a [u8], kind: ErrorKind) -> Self {
+        Self {
+            at,
+            kind,
+            context: Context::default(),
+            cut: false,
+        }
+    }
+}
+
+impl<'a> Debug for ParseError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ParseError")
+            .field("at", &InputDebug(self.at))
+            .field("kind", &self.kind)
+            .field("context", &self.context)
+            .field("cut", &self.cut)
+            .finish()
+    }
+}
+
+impl<'a> Display for ParseError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for label in self.context.iter() {
+            write!(fmt, "{}: ", label)?;
+        }
+        write!(fmt, "{}, {} bytes left", self.kind, self.at.len())
+    }
+}
+
+impl<'a> ContextError<'a> for ParseError<'a> {
+    fn add_context(mut self, _at: &'a [u8], label: &'static str) -> Self {
+        self.context.push(label);
+        self
+    }
+}
+
+/// An error that can be labeled with what was being parsed when it happened. See
+/// [`context`](crate::context).
+pub trait ContextError<'a> {
+    /// Add a label for a parser that started at `at`. Labels are added from the innermost parser
+    /// outwards.
+    fn add_context(self, at: &'a [u8], label: &'static str) -> Self;
+}
+
+/// A stack of up to 8 labels, without allocating. Labels past that are counted but not kept.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Context {
+    /// Labels from the innermost parser outwards
+    labels: [&'static str; 8],
+    len: usize,
+}
+
+impl Context {
+    /// Add a label for an enclosing parser.
+    pub fn push(&mut self, label: &'static str) {
+        if let Some(x) = self.labels.get_mut(self.len) {
+            *x = label;
+        }
+        self.len += 1;
+    }
+
+    /// Iterate over the labels from the outermost parser inwards.
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.labels[..self.len.min(self.labels.len())]
+            .iter()
+            .rev()
+            .copied()
+    }
+
+    /// How many labels were added, including any that weren't kept.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Debug for Context {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// The kind of a [`ParseError`], named after the error it was converted from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    Byte,
+    ByteMap,
+    Satisfy,
+    Enum,
+    Flags,
+    Bool,
+    Take,
+    TakeWhile1,
+    TakeUntil,
+    TakeTill,
+    CStr,
+    Utf8,
+    Utf16,
+    LengthData,
+    Unstuff,
+    Fill,
+    Many1,
+    ManyMN,
+    ManyTill,
+    Finish,
+    Trailing,
+    Tag,
+    SkipUntil,
+    Or,
+    Alt,
+    MapRes,
+    Verify,
+    ParseAt,
+    Not,
+    Limit,
+    Pod,
+    /// From the number parsers, like [`U32LError`]
+    Number,
+    Base64,
+    Hex,
+    Checksum,
+    Encoding,
+    Digest,
+    Lz4,
+    Percent,
+    Url,
+    Timestamp,
+    QuicVarint,
+    CompactSize,
+    Leb128,
+    Varint,
+    VarintOverlong,
+    Vlq,
+    AsciiNum,
+    Bencode,
+    Bitcoin,
+    Csv,
+    Http,
+    Ini,
+    Json,
+    Mqtt,
+    Pcap,
+    Tls,
+    Ws,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::Byte => "expected a byte",
+            Self::ByteMap => "byte not in table",
+            Self::Satisfy => "byte didn't match",
+            Self::Enum => "unknown discriminant",
+            Self::Flags => "unknown flags",
+            Self::Bool => "not a boolean",
+            Self::Take => "not enough bytes",
+            Self::TakeWhile1 => "no bytes matched",
+            Self::TakeUntil => "sequence not found",
+            Self::TakeTill => "terminator not found",
+            Self::CStr => "null terminator not found",
+            Self::Utf8 => "invalid UTF-8",
+            Self::Utf16 => "invalid UTF-16",
+            Self::LengthData => "not enough bytes for length",
+            Self::Unstuff => "invalid byte stuffing",
+            Self::Fill => "input not exactly consumed",
+            Self::Many1 => "expected at least one",
+            Self::ManyMN => "wrong number of repetitions",
+            Self::ManyTill => "terminator not found",
+            Self::Finish => "bytes left over",
+            Self::Trailing => "bytes left over",
+            Self::Tag => "tag didn't match",
+            Self::SkipUntil => "parser never succeeded",
+            Self::Or => "neither parser matched",
+            Self::Alt => "no branch matched",
+            Self::MapRes => "conversion failed",
+            Self::Verify => "verification failed",
+            Self::ParseAt => "offset out of bounds",
+            Self::Not => "parser succeeded",
+            Self::Limit => "parser consumed too much",
+            Self::Pod => "can't cast bytes",
+            Self::Number => "not enough bytes for number",
+            Self::Base64 => "invalid base64",
+            Self::Hex => "invalid hex",
+            Self::Checksum => "checksum mismatch",
+            Self::Encoding => "invalid encoded text",
+            Self::Digest => "digest mismatch",
+            Self::Lz4 => "invalid LZ4",
+            Self::Percent => "invalid percent escape",
+            Self::Url => "invalid URL",
+            Self::Timestamp => "invalid timestamp",
+            Self::QuicVarint => "invalid QUIC varint",
+            Self::CompactSize => "invalid CompactSize",
+            Self::Leb128 => "invalid LEB128",
+            Self::Varint => "incomplete varint",
+            Self::VarintOverlong => "varint too long",
+            Self::Vlq => "invalid variable-length quantity",
+            Self::AsciiNum => "invalid ASCII number",
+            Self::Bencode => "invalid bencode",
+            Self::Bitcoin => "invalid bitcoin data",
+            Self::Csv => "invalid CSV",
+            Self::Http => "invalid HTTP",
+            Self::Ini => "invalid INI",
+            Self::Json => "invalid JSON",
+            Self::Mqtt => "invalid MQTT packet",
+            Self::Pcap => "invalid PCAP",
+            Self::Tls => "invalid TLS",
+            Self::Ws => "invalid WebSocket frame",
+        };
+        fmt.write_str(message)
+    }
+}
+
+/// An error that [`or`](crate::or) and [`alt`](crate::alt) can recover from by trying the next
+/// branch. Errors are recoverable unless they came from [`cut`](crate::cut).
+pub trait Recoverable {
+    fn is_recoverable(&self) -> bool;
+}
+
+impl<'a> Recoverable for ParseError<'a> {
+    fn is_recoverable(&self) -> bool {
+        !self.cut
+    }
+}
+
+impl<'a, ChildError> Recoverable for CutError<'a, ChildError> {
+    fn is_recoverable(&self) -> bool {
+        false
+    }
+}
+
+/// Implement [`Recoverable`] for errors that are always recoverable.
+macro_rules! recoverable {
+    ($($(#[$m:meta])* $name:ident $(<$($param:tt),*>)?;)*) => {
+        $(
+            $(#[$m])*
+            impl$(<$($param),*>)? Recoverable for $name$(<$($param),*>)? {
+                fn is_recoverable(&self) -> bool {
+                    true
+                }
+            }
+        )*
+    };
+}
+
+recoverable! {
+    OrError<'a, Error1, Error2>;
+    AltError<'a, ChildErrors>;
+    VerifyError<'a, Output>;
+    EnumError<'a, Discriminant>;
+    FlagsError<'a, Bits>;
+    TakeWhileError;
+    RestError;
+    Many0Error;
+    SeparatedList0Error;
+    SeparatedListTrailingError;
+    OptError;
+}
+
+/// Implement [`Recoverable`] for errors that hold a child's error, which are recoverable if it is.
+macro_rules! recoverable_child {
+    ($($name:ident;)*) => {
+        $(
+            impl<'a, ChildError: Recoverable> Recoverable for $name<'a, ChildError> {
+                fn is_recoverable(&self) -> bool {
+                    self.child_error.is_recoverable()
+                }
+            }
+        )*
+    };
+}
+
+recoverable_child! {
+    SeqError;
+    TupleError;
+    SeparatedList1Error;
+}
+
+/// Implement [`Recoverable`] for errors that may hold a child's error.
+macro_rules! recoverable_opt_child {
+    ($($name:ident;)*) => {
+        $(
+            impl<'a, ChildError: Recoverable> Recoverable for $name<'a, ChildError> {
+                fn is_recoverable(&self) -> bool {
+                    self.child_error.as_ref().map_or(true, Recoverable::is_recoverable)
+                }
+            }
+        )*
+    };
+}
+
+recoverable_opt_child! {
+    FillError;
+    Many1Error;
+    ManyMNError;
+    ManyTillError;
+}
+
+impl<'a, LengthError: Recoverable> Recoverable for LengthDataError<'a, LengthError> {
+    fn is_recoverable(&self) -> bool {
+        match self {
+            Self::Length(e) => e.is_recoverable(),
+            Self::Data { .. } => true,
+        }
+    }
+}
+
+impl<'a, LengthError: Recoverable, ChildError: Recoverable> Recoverable
+    for LengthCountError<'a, LengthError, ChildError>
+{
+    fn is_recoverable(&self) -> bool {
+        match self {
+            Self::Length(e) => e.is_recoverable(),
+            Self::Child(e) => e.is_recoverable(),
+        }
+    }
+}
+
+impl<'a, ChildError: Recoverable, MapError> Recoverable for MapResError<'a, ChildError, MapError> {
+    fn is_recoverable(&self) -> bool {
+        match self {
+            Self::Child(e) => e.is_recoverable(),
+            Self::Map { .. } => true,
+        }
+    }
+}
+
+/// An error that knows where in the input it happened.
+pub trait Located<'a> {
+    /// The input where the error happened.
+    fn location(&self) -> &'a [u8];
+
+    /// Byte offset of the error from the start of `root`, the original input, or `None` if the
+    /// error didn't happen in `root`.
+    fn offset_in(&self, root: &[u8]) -> Option<usize> {
+        crate::offset_in(root, self.location())
+    }
+}
+
+/// Implement [`Located`] for errors with an `at` field.
+macro_rules! located {
+    ($($name:ident <$($param:tt),*>;)*) => {
+        $(
+            impl<$($param),*> Located<'a> for $name<$($param),*> {
+                fn location(&self) -> &'a [u8] {
+                    self.at
+                }
+            }
+        )*
+    };
+}
+
+located! {
+    ParseError<'a>;
+    SeqError<'a, ChildError>;
+    TupleError<'a, ChildError>;
+    SeparatedList1Error<'a, ChildError>;
+    FillError<'a, ChildError>;
+    Many1Error<'a, ChildError>;
+    ManyMNError<'a, ChildError>;
+    ManyTillError<'a, ChildError>;
+    OrError<'a, Error1, Error2>;
+    AltError<'a, ChildErrors>;
+    VerifyError<'a, Output>;
+    CutError<'a, ChildError>;
+    EnumError<'a, Discriminant>;
+    FlagsError<'a, Bits>;
+}
+
+/// Located at the invalid sequence, rather than the start of the text.
+impl<'a> Located<'a> for Utf8Error<'a> {
+    fn location(&self) -> &'a [u8] {
+        &self.at[self.valid_up_to..]
+    }
+}
+
+impl<'a> Recoverable for Utf8Error<'a> {
+    fn is_recoverable(&self) -> bool {
+        true
+    }
+}
+
+/// Located at the unpaired surrogate, rather than the start of the text.
+impl<'a> Located<'a> for Utf16Error<'a> {
+    fn location(&self) -> &'a [u8] {
+        &self.at[self.valid_up_to..]
+    }
+}
+
+impl<'a> Recoverable for Utf16Error<'a> {
+    fn is_recoverable(&self) -> bool {
+        true
+    }
+}
+
+impl<'a, LengthError: Located<'a>> Located<'a> for LengthDataError<'a, LengthError> {
+    fn location(&self) -> &'a [u8] {
+        match self {
+            Self::Length(e) => e.location(),
+            Self::Data { at, .. } => at,
+        }
+    }
+}
+
+impl<'a, LengthError: Located<'a>, ChildError> Located<'a>
+    for LengthCountError<'a, LengthError, ChildError>
+{
+    fn location(&self) -> &'a [u8] {
+        match self {
+            Self::Length(e) => e.location(),
+            Self::Child(e) => e.at,
+        }
+    }
+}
+
+impl<'a, ChildError: Located<'a>, MapError> Located<'a> for MapResError<'a, ChildError, MapError> {
+    fn location(&self) -> &'a [u8] {
+        match self {
+            Self::Child(e) => e.location(),
+            Self::Map { at, .. } => at,
+        }
+    }
+}
+
+/// Convert errors that only hold where they happened, as `.0` or `.at`, and implement
+/// [`Located`] and [`Recoverable`] for them.
+macro_rules! parse_error_from {
+    ($($(#[$m:meta])* $error:ty, $field:tt => $kind:ident;)*) => {
+        $(
+            $(#[$m])*
+            impl<'a> From<$error> for ParseError<'a> {
+                fn from(x: $error) -> Self {
+                    Self::new(x.$field, ErrorKind::$kind)
+                }
+            }
+
+            $(#[$m])*
+            impl<'a> Located<'a> for $error {
+                fn location(&self) -> &'a [u8] {
+                    self.$field
+                }
+            }
+
+            $(#[$m])*
+            impl<'a> Recoverable for $error {
+                fn is_recoverable(&self) -> bool {
+                    true
+                }
+            }
+        )*
+    };
+}
+
+parse_error_from! {
+    ByteError<'a>, at => Byte;
+    ByteMapError<'a>, at => ByteMap;
+    SatisfyError<'a>, at => Satisfy;
+    BoolError<'a>, at => Bool;
+    TakeError<'a>, 0 => Take;
+    TakeWhile1Error<'a>, 0 => TakeWhile1;
+    TakeUntilError<'a>, 0 => TakeUntil;
+    TakeTillError<'a>, 0 => TakeTill;
+    CStrError<'a>, at => CStr;
+    UnstuffError<'a>, 0 => Unstuff;
+    FinishError<'a>, 0 => Finish;
+    TrailingError<'a>, at => Trailing;
+    TagError<'a>, 0 => Tag;
+    SkipUntilError<'a>, 0 => SkipUntil;
+    ParseAtError<'a>, at => ParseAt;
+    NotError<'a>, 0 => Not;
+    LimitError<'a>, at => Limit;
+    #[cfg(feature = "bytemuck")]
+    PodError<'a>, at => Pod;
+    SByteError<'a>, 0 => Number;
+    U16LError<'a>, 0 => Number;
+    I16LError<'a>, 0 => Number;
+    U16BError<'a>, 0 => Number;
+    I16BError<'a>, 0 => Number;
+    U32LError<'a>, 0 => Number;
+    I32LError<'a>, 0 => Number;
+    U32BError<'a>, 0 => Number;
+    I32BError<'a>, 0 => Number;
+    U64LError<'a>, 0 => Number;
+    I64LError<'a>, 0 => Number;
+    U64BError<'a>, 0 => Number;
+    I64BError<'a>, 0 => Number;
+    U128LError<'a>, 0 => Number;
+    I128LError<'a>, 0 => Number;
+    U128BError<'a>, 0 => Number;
+    I128BError<'a>, 0 => Number;
+    F32LError<'a>, 0 => Number;
+    F32BError<'a>, 0 => Number;
+    F64LError<'a>, 0 => Number;
+    F64BError<'a>, 0 => Number;
+    U24LError<'a>, 0 => Number;
+    I24LError<'a>, 0 => Number;
+    U24BError<'a>, 0 => Number;
+    I24BError<'a>, 0 => Number;
+    U48LError<'a>, 0 => Number;
+    U48BError<'a>, 0 => Number;
+    F16LError<'a>, 0 => Number;
+    F16BError<'a>, 0 => Number;
+    U16NError<'a>, 0 => Number;
+    I16NError<'a>, 0 => Number;
+    U32NError<'a>, 0 => Number;
+    I32NError<'a>, 0 => Number;
+    U64NError<'a>, 0 => Number;
+    I64NError<'a>, 0 => Number;
+    U128NError<'a>, 0 => Number;
+    I128NError<'a>, 0 => Number;
+    F32NError<'a>, 0 => Number;
+    F64NError<'a>, 0 => Number;
+    NumError<'a>, at => Number;
+    #[cfg(feature = "alloc")]
+    Base64Error<'a>, at => Base64;
+    HexError<'a>, at => Hex;
+    ChecksumError<'a>, at => Checksum;
+    #[cfg(feature = "encoding_rs")]
+    EncodingError<'a>, at => Encoding;
+    #[cfg(feature = "digest")]
+    DigestError<'a>, at => Digest;
+    #[cfg(feature = "lz4")]
+    Lz4Error<'a>, at => Lz4;
+    PercentError<'a>, at => Percent;
+    UrlError<'a>, 0 => Url;
+    TimestampError<'a>, at => Timestamp;
+    QuicVarintError<'a>, 0 => QuicVarint;
+    CompactSizeError<'a>, 0 => CompactSize;
+    Leb128Error<'a>, 0 => Leb128;
+    VarintError<'a>, 0 => Varint;
+    VarintOverlongError<'a>, 0 => VarintOverlong;
+    VlqError<'a>, 0 => Vlq;
+    AsciiNumError<'a>, at => AsciiNum;
+    #[cfg(feature = "alloc")]
+    bencode::BencodeError<'a>, 0 => Bencode;
+    #[cfg(feature = "alloc")]
+    bitcoin::BitcoinError<'a>, 0 => Bitcoin;
+    #[cfg(feature = "alloc")]
+    csv::CsvError<'a>, 0 => Csv;
+    http1::HttpError<'a>, 0 => Http;
+    ini::IniError<'a>, 0 => Ini;
+    #[cfg(feature = "json")]
+    json::JsonError<'a>, 0 => Json;
+    mqtt::MqttError<'a>, 0 => Mqtt;
+    pcap::PcapError<'a>, 0 => Pcap;
+    tls::TlsError<'a>, 0 => Tls;
+    ws::WsError<'a>, 0 => Ws;
+}
+
+impl<'a> From<Utf8Error<'a>> for ParseError<'a> {
+    fn from(x: Utf8Error<'a>) -> Self {
+        Self::new(x.location(), ErrorKind::Utf8)
+    }
+}
+
+impl<'a> From<Utf16Error<'a>> for ParseError<'a> {
+    fn from(x: Utf16Error<'a>) -> Self {
+        Self::new(x.location(), ErrorKind::Utf16)
+    }
+}
+
+/// Convert the errors of parsers that can't fail.
+macro_rules! parse_error_never {
+    ($($error:ty;)*) => {
+        $(
+            impl<'a> From<$error> for ParseError<'a> {
+                fn from(x: $error) -> Self {
+                    match x {}
+                }
+            }
+        )*
+    };
+}
+
+parse_error_never! {
+    TakeWhileError;
+    RestError;
+    Many0Error;
+    SeparatedList0Error;
+    SeparatedListTrailingError;
+    OptError;
+}
+
+impl<'a, LengthError> From<LengthDataError<'a, LengthError>> for ParseError<'a>
+where
+    ParseError<'a>: From<LengthError>,
+{
+    fn from(x: LengthDataError<'a, LengthError>) -> Self {
+        match x {
+            LengthDataError::Length(e) => e.into(),
+            LengthDataError::Data { at, .. } => Self::new(at, ErrorKind::LengthData),
+        }
+    }
+}
+
+impl<'a, ChildError> From<CutError<'a, ChildError>> for ParseError<'a>
+where
+    ParseError<'a>: From<ChildError>,
+{
+    fn from(x: CutError<'a, ChildError>) -> Self {
+        let mut error = Self::from(x.child_error);
+        error.cut = true;
+        error
+    }
+}
+
+impl<'a, ChildError> From<SeqError<'a, ChildError>> for ParseError<'a>
+where
+    ParseError<'a>: From<ChildError>,
+{
+    fn from(x: SeqError<'a, ChildError>) -> Self {
+        x.child_error.into()
+    }
+}
+
+impl<'a, LengthError, ChildError> From<LengthCountError<'a, LengthError, ChildError>>
+    for ParseError<'a>
+where
+    ParseError<'a>: From<LengthError> + From<ChildError>,
+{
+    fn from(x: LengthCountError<'a, LengthError, ChildError>) -> Self {
+        match x {
+            LengthCountError::Length(e) => e.into(),
+            LengthCountError::Child(e) => e.into(),
+        }
+    }
+}
+
+impl<'a, ChildError> From<TupleError<'a, ChildError>> for ParseError<'a>
+where
+    ParseError<'a>: From<ChildError>,
+{
+    fn from(x: TupleError<'a, ChildError>) -> Self {
+        x.child_error.into()
+    }
+}
+
+impl<'a, ChildError> From<SeparatedList1Error<'a, ChildError>> for ParseError<'a>
+where
+    ParseError<'a>: From<ChildError>,
+{
+    fn from(x: SeparatedList1Error<'a, ChildError>) -> Self {
+        x.child_error.into()
+    }
+}
+
+/// Convert errors with an optional child error, using the child's error if there is one.
+macro_rules! parse_error_child {
+    ($($error:ident => $kind:ident;)*) => {
+        $(
+            impl<'a, ChildError> From<$error<'a, ChildError>> for ParseError<'a>
+            where
+                ParseError<'a>: From<ChildError>,
+            {
+                fn from(x: $error<'a, ChildError>) -> Self {
+                    match x.child_error {
+                        Some(e) => e.into(),
+                        None => Self::new(x.at, ErrorKind::$kind),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+parse_error_child! {
+    FillError => Fill;
+    Many1Error => Many1;
+    ManyMNError => ManyMN;
+    ManyTillError => ManyTill;
+}
+
+impl<'a, Error1, Error2> From<OrError<'a, Error1, Error2>> for ParseError<'a> {
+    fn from(x: OrError<'a, Error1, Error2>) -> Self {
+        Self::new(x.at, ErrorKind::Or)
+    }
+}
+
+impl<'a, ChildErrors> From<AltError<'a, ChildErrors>> for ParseError<'a> {
+    fn from(x: AltError<'a, ChildErrors>) -> Self {
+        Self::new(x.at, ErrorKind::Alt)
+    }
+}
+
+impl<'a, ChildError, MapError> From<MapResError<'a, ChildError, MapError>> for ParseError<'a>
+where
+    ParseError<'a>: From<ChildError>,
+{
+    fn from(x: MapResError<'a, ChildError, MapError>) -> Self {
+        match x {
+            MapResError::Child(e) => e.into(),
+            MapResError::Map { at, .. } => Self::new(at, ErrorKind::MapRes),
+        }
+    }
+}
+
+impl<'a, Discriminant> From<EnumError<'a, Discriminant>> for ParseError<'a> {
+    fn from(x: EnumError<'a, Discriminant>) -> Self {
+        Self::new(x.at, ErrorKind::Enum)
+    }
+}
+
+impl<'a, Bits> From<FlagsError<'a, Bits>> for ParseError<'a> {
+    fn from(x: FlagsError<'a, Bits>) -> Self {
+        Self::new(x.at, ErrorKind::Flags)
+    }
+}
+
+impl<'a, Output> From<VerifyError<'a, Output>> for ParseError<'a> {
+    fn from(x: VerifyError<'a, Output>) -> Self {
+        Self::new(x.at, ErrorKind::Verify)
+    }
+}
+
+std_error! {
+    ParseError<'a>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::byte;
+
+    #[test]
+    fn byte_error_offset() {
+        let input = b"ab";
+        let (rest, _) = byte::<ByteError>(&input[..]);
+        let (rest, _) = byte::<ByteError>(rest);
+        let (_, x) = byte::<ByteError>(rest);
+        assert_eq!(x.unwrap_err().offset_in(input), Some(2));
+        let (_, x) = byte::<ParseError>(rest);
+        let x = x.unwrap_err();
+        assert_eq!(x.kind, ErrorKind::Byte);
+        assert_eq!(x.offset_in(input), Some(2));
+    }
+}
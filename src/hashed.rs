@@ -0,0 +1,66 @@
+use crate::{InputDebug, Step};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+use digest::Digest;
+
+#[derive(Clone, PartialEq)]
+pub struct DigestError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The digest stored after the region
+    pub expected: Vec<u8>,
+    /// The digest of the region's bytes
+    pub found: Vec<u8>,
+}
+
+impl<'a> Debug for DigestError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("DigestError")
+            .field("at", &InputDebug(self.at))
+            .field("expected", &self.expected)
+            .field("found", &self.found)
+            .finish()
+    }
+}
+
+impl<'a> Display for DigestError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "digest mismatch")
+    }
+}
+
+/// Run a parser over a region, then a parser for the digest stored after it, failing if it isn't
+/// the digest of the bytes the region parser consumed. The given hasher is cloned for each run, so
+/// it may be keyed or salted already.
+pub fn hashed<'a, D, Output, Stored, Error, RegionError, StoredError>(
+    region: impl Fn(&'a [u8]) -> Step<'a, Output, RegionError>,
+    hasher: D,
+    stored: impl Fn(&'a [u8]) -> Step<'a, Stored, StoredError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error>
+where
+    D: Digest + Clone,
+    Stored: AsRef<[u8]>,
+    Error: From<RegionError> + From<StoredError> + From<DigestError<'a>>,
+{
+    move |input| {
+        let (rest, x) = step!(input, (region)(input));
+        let consumed = &input[..input.len() - rest.len()];
+        let (rest, expected) = step!(input, (stored)(rest));
+        let found = hasher.clone().chain_update(consumed).finalize();
+        match expected.as_ref() == found.as_slice() {
+            true => (rest, Ok(x)),
+            false => {
+                let e = DigestError {
+                    at: consumed,
+                    expected: expected.as_ref().to_vec(),
+                    found: found.to_vec(),
+                };
+                (input, Err(e.into()))
+            }
+        }
+    }
+}
+
+std_error! {
+    DigestError<'a>;
+}
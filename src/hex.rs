@@ -0,0 +1,292 @@
+use crate::{take, InputDebug, Step, TakeError};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct HexError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// Offset of the invalid character from where the error happened
+    pub offset: usize,
+}
+
+impl<'a> Debug for HexError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("HexError")
+            .field("at", &InputDebug(self.at))
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+impl<'a> Display for HexError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid hex at offset {}", self.offset)
+    }
+}
+
+impl<'a> From<TakeError<'a>> for HexError<'a> {
+    fn from(x: TakeError<'a>) -> Self {
+        Self { at: x.0, offset: 0 }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ChecksumError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The checksum stored in the record
+    pub expected: u8,
+    /// The checksum of the record's bytes
+    pub found: u8,
+}
+
+impl<'a> Debug for ChecksumError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ChecksumError")
+            .field("at", &InputDebug(self.at))
+            .field("expected", &self.expected)
+            .field("found", &self.found)
+            .finish()
+    }
+}
+
+impl<'a> Display for ChecksumError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "checksum mismatch, expected {:#04x} but found {:#04x}",
+            self.expected, self.found
+        )
+    }
+}
+
+pub(crate) fn digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Decode hex text, or output the offset of the invalid character.
+#[cfg(feature = "alloc")]
+fn decode(text: &[u8]) -> Result<Vec<u8>, usize> {
+    let mut out = Vec::with_capacity(text.len() / 2);
+    for (i, pair) in text.chunks(2).enumerate() {
+        match pair {
+            &[hi, lo] => match (digit(hi), digit(lo)) {
+                (Some(hi), Some(lo)) => out.push(hi << 4 | lo),
+                (None, _) => return Err(i * 2),
+                (_, None) => return Err(i * 2 + 1),
+            },
+            _ => return Err(i * 2 + 1),
+        }
+    }
+    Ok(out)
+}
+
+/// Take N characters of hex text, outputting the decoded bytes.
+#[cfg(feature = "alloc")]
+pub fn hex<'a, Error: From<HexError<'a>>>(
+    count: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<u8>, Error> {
+    move |input| match take::<HexError>(count)(input) {
+        (rest, Ok(text)) => match decode(text) {
+            Ok(x) => (rest, Ok(x)),
+            Err(offset) => (input, Err(HexError { at: input, offset }.into())),
+        },
+        (_, Err(e)) => (input, Err(e.into())),
+    }
+}
+
+/// Take N bytes encoded as hex text, so twice as many characters, outputting the decoded bytes.
+#[cfg(feature = "alloc")]
+pub fn hex_bytes<'a, Error: From<HexError<'a>>>(
+    count: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<u8>, Error> {
+    hex(count.saturating_mul(2))
+}
+
+/// Parse two characters of hex text as a byte.
+pub fn hex_byte<'a, Error: From<HexError<'a>>>(input: &'a [u8]) -> Step<'a, u8, Error> {
+    match take::<HexError>(2)(input) {
+        (rest, Ok(pair)) => match (digit(pair[0]), digit(pair[1])) {
+            (Some(hi), Some(lo)) => (rest, Ok(hi << 4 | lo)),
+            (None, _) => (
+                input,
+                Err(HexError {
+                    at: input,
+                    offset: 0,
+                }
+                .into()),
+            ),
+            (_, None) => (
+                input,
+                Err(HexError {
+                    at: input,
+                    offset: 1,
+                }
+                .into()),
+            ),
+        },
+        (_, Err(e)) => (input, Err(e.into())),
+    }
+}
+
+/// Take a record with a start character and a hex byte count, decoding it and verifying the
+/// checksum over all but the last byte.
+#[cfg(feature = "alloc")]
+fn record<'a, Error: From<HexError<'a>> + From<ChecksumError<'a>>>(
+    input: &'a [u8],
+    start: u8,
+    prefix: usize,
+    len: impl Fn(u8) -> usize,
+    checksum: impl Fn(&[u8]) -> u8,
+) -> Step<'a, Vec<u8>, Error> {
+    if input.first() != Some(&start) {
+        return (
+            input,
+            Err(HexError {
+                at: input,
+                offset: 0,
+            }
+            .into()),
+        );
+    }
+    let count = match hex_byte::<HexError>(&input[1 + prefix..]) {
+        (_, Ok(x)) => x,
+        (_, Err(e)) => {
+            let offset = 1 + prefix + e.offset;
+            return (input, Err(HexError { at: input, offset }.into()));
+        }
+    };
+    let (rest, bytes) = match hex::<HexError>(len(count) * 2)(&input[1 + prefix..]) {
+        (rest, Ok(x)) => (rest, x),
+        (_, Err(e)) => {
+            let offset = 1 + prefix + e.offset;
+            return (input, Err(HexError { at: input, offset }.into()));
+        }
+    };
+    let (&expected, body) = bytes.split_last().unwrap();
+    let found = checksum(body);
+    match found == expected {
+        true => (rest, Ok(bytes)),
+        false => {
+            let error = ChecksumError {
+                at: input,
+                expected,
+                found,
+            };
+            (input, Err(error.into()))
+        }
+    }
+}
+
+/// A record of an Intel HEX file.
+#[cfg(feature = "alloc")]
+pub struct IntelHexRecord {
+    /// The record type
+    pub kind: u8,
+    /// The load offset of the data
+    pub address: u16,
+    /// The data of the record
+    pub data: Vec<u8>,
+}
+
+/// Parse an Intel HEX record, verifying its checksum. The line ending is not consumed.
+#[cfg(feature = "alloc")]
+pub fn intel_hex_record<'a, Error: From<HexError<'a>> + From<ChecksumError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, IntelHexRecord, Error> {
+    let (rest, bytes) = record::<Error>(
+        input,
+        b':',
+        0,
+        |count| count as usize + 5,
+        |body| {
+            body.iter()
+                .fold(0u8, |a, &b| a.wrapping_add(b))
+                .wrapping_neg()
+        },
+    );
+    let bytes = match bytes {
+        Ok(x) => x,
+        Err(e) => return (rest, Err(e)),
+    };
+    let record = IntelHexRecord {
+        kind: bytes[3],
+        address: u16::from_be_bytes([bytes[1], bytes[2]]),
+        data: bytes[4..bytes.len() - 1].to_vec(),
+    };
+    (rest, Ok(record))
+}
+
+/// A record of a Motorola S-record file.
+#[cfg(feature = "alloc")]
+pub struct SRecord {
+    /// The record type, from 0 to 9
+    pub kind: u8,
+    /// The address of the data
+    pub address: u32,
+    /// The data of the record
+    pub data: Vec<u8>,
+}
+
+/// Parse a Motorola S-record, verifying its checksum. The line ending is not consumed.
+#[cfg(feature = "alloc")]
+pub fn s_record<'a, Error: From<HexError<'a>> + From<ChecksumError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, SRecord, Error> {
+    let (kind, address_len) = match input.get(1).copied().and_then(digit) {
+        Some(kind @ (0 | 1 | 5 | 9)) => (kind, 2),
+        Some(kind @ (2 | 6 | 8)) => (kind, 3),
+        Some(kind @ (3 | 7)) => (kind, 4),
+        _ => {
+            return (
+                input,
+                Err(HexError {
+                    at: input,
+                    offset: 1,
+                }
+                .into()),
+            )
+        }
+    };
+    let (rest, bytes) = record::<Error>(
+        input,
+        b'S',
+        1,
+        |count| count as usize + 1,
+        |body| !body.iter().fold(0u8, |a, &b| a.wrapping_add(b)),
+    );
+    let bytes = match bytes {
+        Ok(x) => x,
+        Err(e) => return (rest, Err(e)),
+    };
+    if bytes.len() < address_len + 2 {
+        return (
+            input,
+            Err(HexError {
+                at: input,
+                offset: 2,
+            }
+            .into()),
+        );
+    }
+    let mut address = [0; 4];
+    address[4 - address_len..].copy_from_slice(&bytes[1..1 + address_len]);
+    let record = SRecord {
+        kind,
+        address: u32::from_be_bytes(address),
+        data: bytes[1 + address_len..bytes.len() - 1].to_vec(),
+    };
+    (rest, Ok(record))
+}
+
+std_error! {
+    HexError<'a>;
+    ChecksumError<'a>;
+}
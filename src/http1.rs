@@ -0,0 +1,201 @@
+//! HTTP/1.x message parsers, following RFC 9112.
+
+use crate::{InputDebug, Step};
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct HttpError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for HttpError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("HttpError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for HttpError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid HTTP")
+    }
+}
+
+/// An HTTP version, like `(1, 1)` for `HTTP/1.1`.
+pub type Version = (u8, u8);
+
+/// The first line of a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RequestLine<'a> {
+    /// The method, like `GET`
+    pub method: &'a [u8],
+    /// The request target, like `/index.html`
+    pub target: &'a [u8],
+    pub version: Version,
+}
+
+/// The first line of a response.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StatusLine<'a> {
+    pub version: Version,
+    /// The status code, like `404`
+    pub code: u16,
+    /// The reason phrase, like `Not Found`, which may be empty
+    pub reason: &'a [u8],
+}
+
+fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
+/// Visible characters, spaces, tabs, and `obs-text`.
+fn is_field_char(b: u8) -> bool {
+    b == b' ' || b == b'\t' || (0x21..=0x7e).contains(&b) || b >= 0x80
+}
+
+/// Split off the longest prefix where `f` holds.
+fn split_while(input: &[u8], f: impl Fn(u8) -> bool) -> (&[u8], &[u8]) {
+    let len = input.iter().position(|&b| !f(b)).unwrap_or(input.len());
+    input.split_at(len)
+}
+
+type Parsed<'a, T> = Result<(&'a [u8], T), &'a [u8]>;
+
+fn token(input: &[u8]) -> Parsed<'_, &[u8]> {
+    match split_while(input, is_tchar) {
+        ([], _) => Err(input),
+        (token, rest) => Ok((rest, token)),
+    }
+}
+
+fn expect<'a>(input: &'a [u8], prefix: &[u8]) -> Result<&'a [u8], &'a [u8]> {
+    input.strip_prefix(prefix).ok_or(input)
+}
+
+fn version(input: &[u8]) -> Parsed<'_, Version> {
+    let rest = expect(input, b"HTTP/")?;
+    match rest {
+        [major @ b'0'..=b'9', b'.', minor @ b'0'..=b'9', rest @ ..] => {
+            Ok((rest, (major - b'0', minor - b'0')))
+        }
+        _ => Err(rest),
+    }
+}
+
+fn finish<'a, Output, Error: From<HttpError<'a>>>(
+    input: &'a [u8],
+    result: Parsed<'a, Output>,
+) -> Step<'a, Output, Error> {
+    match result {
+        Ok((rest, x)) => (rest, Ok(x)),
+        Err(at) => (input, Err(HttpError(at).into())),
+    }
+}
+
+/// Parse a request line, like `GET /index.html HTTP/1.1`, and its CRLF.
+pub fn request_line<'a, Error: From<HttpError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, RequestLine<'a>, Error> {
+    let result = token(input).and_then(|(rest, method)| {
+        let rest = expect(rest, b" ")?;
+        let (target, rest) = split_while(rest, |b| (0x21..=0x7e).contains(&b) || b >= 0x80);
+        if target.is_empty() {
+            return Err(rest);
+        }
+        let rest = expect(rest, b" ")?;
+        let (rest, version) = version(rest)?;
+        let rest = expect(rest, b"\r\n")?;
+        let line = RequestLine {
+            method,
+            target,
+            version,
+        };
+        Ok((rest, line))
+    });
+    finish(input, result)
+}
+
+/// Parse a status line, like `HTTP/1.1 404 Not Found`, and its CRLF.
+pub fn status_line<'a, Error: From<HttpError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, StatusLine<'a>, Error> {
+    let result = version(input).and_then(|(rest, version)| {
+        let rest = expect(rest, b" ")?;
+        let code = match rest {
+            [a @ b'0'..=b'9', b @ b'0'..=b'9', c @ b'0'..=b'9', ..] => [a, b, c]
+                .iter()
+                .fold(0, |n, &&d| n * 10 + (d - b'0') as u16),
+            _ => return Err(rest),
+        };
+        let rest = expect(&rest[3..], b" ")?;
+        let (reason, rest) = split_while(rest, is_field_char);
+        let rest = expect(rest, b"\r\n")?;
+        let line = StatusLine {
+            version,
+            code,
+            reason,
+        };
+        Ok((rest, line))
+    });
+    finish(input, result)
+}
+
+/// Parse a header field, like `Content-Length: 42`, and its CRLF, outputting the name and the
+/// value without surrounding whitespace. Values folded onto the next line are rejected.
+#[allow(clippy::type_complexity)]
+pub fn header_field<'a, Error: From<HttpError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, (&'a [u8], &'a [u8]), Error> {
+    let result = token(input).and_then(|(rest, name)| {
+        let rest = expect(rest, b":")?;
+        let (value, rest) = split_while(rest, is_field_char);
+        let rest = expect(rest, b"\r\n")?;
+        if let Some(b' ' | b'\t') = rest.first() {
+            return Err(rest);
+        }
+        let value = match value.iter().position(|&b| b != b' ' && b != b'\t') {
+            Some(start) => {
+                let end = value
+                    .iter()
+                    .rposition(|&b| b != b' ' && b != b'\t')
+                    .unwrap();
+                &value[start..end + 1]
+            }
+            None => &value[value.len()..],
+        };
+        Ok((rest, (name, value)))
+    });
+    finish(input, result)
+}
+
+/// Parse the empty line that ends the header fields.
+pub fn headers_end<'a, Error: From<HttpError<'a>>>(input: &'a [u8]) -> Step<'a, (), Error> {
+    finish(input, expect(input, b"\r\n").map(|rest| (rest, ())))
+}
+
+/// Parse the size line of a chunk in the chunked transfer coding, skipping any chunk extensions.
+pub fn chunk_size<'a, Error: From<HttpError<'a>>>(input: &'a [u8]) -> Step<'a, u64, Error> {
+    let (digits, rest) = split_while(input, |b| b.is_ascii_hexdigit());
+    let result = match digits {
+        [] => Err(input),
+        // More than 16 digits would overflow, other than leading zeros
+        _ if digits.iter().skip_while(|&&b| b == b'0').count() > 16 => Err(input),
+        _ => {
+            let size = digits.iter().fold(0, |n, &b| {
+                n << 4 | (b as char).to_digit(16).unwrap_or_default() as u64
+            });
+            let rest = match rest.first() {
+                Some(b';') => split_while(rest, |b| b != b'\r' && b != b'\n').1,
+                _ => rest,
+            };
+            expect(rest, b"\r\n").map(|rest| (rest, size))
+        }
+    };
+    finish(input, result)
+}
+
+std_error! {
+    HttpError<'a>;
+}
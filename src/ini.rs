@@ -0,0 +1,122 @@
+//! `key = value` and INI-style line parsers.
+
+use crate::{InputDebug, Step};
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct IniError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for IniError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("IniError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for IniError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid INI line")
+    }
+}
+
+/// Split off a line, without its `\n` or `\r\n` line ending.
+fn split_line(input: &[u8]) -> (&[u8], &[u8]) {
+    match input.iter().position(|&b| b == b'\n') {
+        Some(len) => {
+            let line = &input[..len];
+            (line.strip_suffix(b"\r").unwrap_or(line), &input[len + 1..])
+        }
+        None => (input, &input[input.len()..]),
+    }
+}
+
+fn trim(text: &[u8]) -> &[u8] {
+    let start = text
+        .iter()
+        .position(|&b| b != b' ' && b != b'\t')
+        .unwrap_or(text.len());
+    let end = text
+        .iter()
+        .rposition(|&b| b != b' ' && b != b'\t')
+        .map_or(start, |x| x + 1);
+    &text[start..end]
+}
+
+/// Parse a `key = value` line with the given delimiter, outputting the key and value without
+/// surrounding whitespace. The line ending is consumed.
+#[allow(clippy::type_complexity)]
+pub fn kv_line<'a, Error: From<IniError<'a>>>(
+    delimiter: u8,
+) -> impl Fn(&'a [u8]) -> Step<'a, (&'a [u8], &'a [u8]), Error> {
+    move |input| {
+        let (line, rest) = split_line(input);
+        match line.iter().position(|&b| b == delimiter) {
+            Some(i) if !trim(&line[..i]).is_empty() => {
+                (rest, Ok((trim(&line[..i]), trim(&line[i + 1..]))))
+            }
+            _ => (input, Err(IniError(input).into())),
+        }
+    }
+}
+
+/// Parse a `[section]` header line, outputting the section name without surrounding whitespace.
+/// The line ending is consumed.
+pub fn section<'a, Error: From<IniError<'a>>>(input: &'a [u8]) -> Step<'a, &'a [u8], Error> {
+    let (line, rest) = split_line(input);
+    match trim(line) {
+        [b'[', name @ .., b']'] => (rest, Ok(trim(name))),
+        _ => (input, Err(IniError(input).into())),
+    }
+}
+
+/// Parse a comment line starting with `;` or `#`, outputting the text after it. The line ending
+/// is consumed.
+pub fn comment<'a, Error: From<IniError<'a>>>(input: &'a [u8]) -> Step<'a, &'a [u8], Error> {
+    let (line, rest) = split_line(input);
+    match trim(line) {
+        [b';' | b'#', text @ ..] => (rest, Ok(trim(text))),
+        _ => (input, Err(IniError(input).into())),
+    }
+}
+
+/// A line of an INI file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Line<'a> {
+    Section(&'a [u8]),
+    Pair(&'a [u8], &'a [u8]),
+    Comment(&'a [u8]),
+    Blank,
+}
+
+/// Parse any line of an INI file, using the given delimiter for `key = value` lines. The line
+/// ending is consumed.
+pub fn line<'a, Error: From<IniError<'a>>>(
+    delimiter: u8,
+) -> impl Fn(&'a [u8]) -> Step<'a, Line<'a>, Error> {
+    move |input| {
+        if input.is_empty() {
+            return (input, Err(IniError(input).into()));
+        }
+        if let (rest, Ok(x)) = section::<IniError>(input) {
+            return (rest, Ok(Line::Section(x)));
+        }
+        if let (rest, Ok(x)) = comment::<IniError>(input) {
+            return (rest, Ok(Line::Comment(x)));
+        }
+        if let (rest, Ok((key, value))) = kv_line::<IniError>(delimiter)(input) {
+            return (rest, Ok(Line::Pair(key, value)));
+        }
+        match split_line(input) {
+            (line, rest) if trim(line).is_empty() => (rest, Ok(Line::Blank)),
+            _ => (input, Err(IniError(input).into())),
+        }
+    }
+}
+
+std_error! {
+    IniError<'a>;
+}
@@ -0,0 +1,97 @@
+use crate::Step;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+/// A cheap handle to a value stored in an [`Interner`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// The symbol for the value stored at an index.
+    fn new(index: usize) -> Self {
+        Self(u32::try_from(index).expect("too many values to intern"))
+    }
+}
+
+/// Deduplicates parser outputs, handing out a [`Symbol`] for each distinct value.
+pub struct Interner<T> {
+    symbols: RefCell<BTreeMap<T, Symbol>>,
+    values: RefCell<Vec<T>>,
+}
+
+impl<T: Ord + Copy> Interner<T> {
+    pub fn new() -> Self {
+        Self {
+            symbols: RefCell::new(BTreeMap::new()),
+            values: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Get the symbol for a value, storing the value if it hasn't been seen before.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a new value is stored when there are already `u32::MAX + 1` of them.
+    pub fn intern(&self, value: T) -> Symbol {
+        *self.symbols.borrow_mut().entry(value).or_insert_with(|| {
+            let mut values = self.values.borrow_mut();
+            let symbol = Symbol::new(values.len());
+            values.push(value);
+            symbol
+        })
+    }
+
+    /// Get the value of a symbol.
+    pub fn resolve(&self, symbol: Symbol) -> Option<T> {
+        self.values.borrow().get(symbol.0 as usize).copied()
+    }
+
+    /// How many distinct values have been interned.
+    pub fn len(&self) -> usize {
+        self.values.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Ord + Copy> Default for Interner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Intern a parser's output, outputting its [`Symbol`].
+pub fn intern<'a, 'b, Output: Ord + Copy, Error>(
+    interner: &'b Interner<Output>,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, Error> + 'b,
+) -> impl Fn(&'a [u8]) -> Step<'a, Symbol, Error> + 'b {
+    move |input| {
+        let (rest, result) = (child)(input);
+        (rest, result.map(|x| interner.intern(x)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern_dedup() {
+        let interner = Interner::new();
+        let a = interner.intern(b'a');
+        assert_eq!(interner.intern(b'b'), Symbol(1));
+        assert_eq!(interner.intern(b'a'), a);
+        assert_eq!(interner.resolve(a), Some(b'a'));
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    #[test]
+    #[should_panic(expected = "too many values to intern")]
+    fn symbol_overflow() {
+        Symbol::new(u32::MAX as usize + 1);
+    }
+}
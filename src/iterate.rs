@@ -0,0 +1,49 @@
+use crate::Step;
+use core::marker::PhantomData;
+
+/// Iterator from [`iterate`].
+pub struct Iterate<'a, P, Output, Error> {
+    child: P,
+    input: &'a [u8],
+    done: bool,
+    types: PhantomData<fn() -> (Output, Error)>,
+}
+
+impl<'a, P, Output, Error> Iterate<'a, P, Output, Error> {
+    /// The bytes left to parse.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.input
+    }
+}
+
+impl<'a, P, Output, Error> Iterator for Iterate<'a, P, Output, Error>
+where
+    P: Fn(&'a [u8]) -> Step<'a, Output, Error>,
+{
+    type Item = Result<Output, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.input.is_empty() {
+            return None;
+        }
+        let (rest, result) = (self.child)(self.input);
+        // Stop after an error, or a success that didn't consume anything and would repeat forever
+        self.done = result.is_err() || rest.len() == self.input.len();
+        self.input = rest;
+        Some(result)
+    }
+}
+
+/// Run a parser repeatedly over the input as an iterator, until the input is used up or the
+/// parser fails, which is yielded last. Use [`Iterate::remaining`] to get what's left after.
+pub fn iterate<'a, Output, Error, P>(input: &'a [u8], child: P) -> Iterate<'a, P, Output, Error>
+where
+    P: Fn(&'a [u8]) -> Step<'a, Output, Error>,
+{
+    Iterate {
+        child,
+        input,
+        done: false,
+        types: PhantomData,
+    }
+}
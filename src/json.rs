@@ -0,0 +1,261 @@
+//! JSON parsers. **Requires the `json` feature**
+
+use crate::{InputDebug, Step};
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::str;
+
+/// How deep arrays and objects may be nested.
+const MAX_DEPTH: usize = 128;
+
+#[derive(Clone, PartialEq)]
+pub struct JsonError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for JsonError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("JsonError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for JsonError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid JSON")
+    }
+}
+
+/// A JSON value, borrowing from the input where possible.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value<'a> {
+    Null,
+    Bool(bool),
+    Number(Number<'a>),
+    String(Cow<'a, str>),
+    Array(Vec<Value<'a>>),
+    /// Members in the order they appear, including any duplicate keys
+    Object(Vec<(Cow<'a, str>, Value<'a>)>),
+}
+
+/// A JSON number, kept as its text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Number<'a>(
+    /// The number's text
+    pub &'a str,
+);
+
+impl<'a> Number<'a> {
+    pub fn as_f64(&self) -> f64 {
+        // The text was already validated as a JSON number, which is also valid for f64.
+        self.0.parse().unwrap_or(f64::NAN)
+    }
+
+    /// The number as an integer, or `None` if it has a fraction or exponent, or doesn't fit.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.0.parse().ok()
+    }
+
+    /// The number as an integer, or `None` if it has a fraction or exponent, or doesn't fit.
+    pub fn as_u64(&self) -> Option<u64> {
+        self.0.parse().ok()
+    }
+}
+
+fn skip_whitespace(input: &[u8]) -> &[u8] {
+    let len = input
+        .iter()
+        .position(|b| !matches!(b, b' ' | b'\t' | b'\n' | b'\r'))
+        .unwrap_or(input.len());
+    &input[len..]
+}
+
+type Parsed<'a, T> = Result<(&'a [u8], T), &'a [u8]>;
+
+fn digits(input: &[u8]) -> &[u8] {
+    let len = input
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .unwrap_or(input.len());
+    &input[len..]
+}
+
+fn number(input: &[u8]) -> Parsed<'_, Number<'_>> {
+    let mut rest = input.strip_prefix(b"-").unwrap_or(input);
+    rest = match rest.first() {
+        Some(b'0') => &rest[1..],
+        Some(b'1'..=b'9') => digits(rest),
+        _ => return Err(rest),
+    };
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        rest = digits(fraction);
+        if rest.len() == fraction.len() {
+            return Err(rest);
+        }
+    }
+    if let Some(exponent) = rest.strip_prefix(b"e").or_else(|| rest.strip_prefix(b"E")) {
+        let exponent = match exponent.first() {
+            Some(b'+' | b'-') => &exponent[1..],
+            _ => exponent,
+        };
+        rest = digits(exponent);
+        if rest.len() == exponent.len() {
+            return Err(rest);
+        }
+    }
+    let text = &input[..input.len() - rest.len()];
+    // Only ASCII was accepted above.
+    let text = str::from_utf8(text).map_err(|_| input)?;
+    Ok((rest, Number(text)))
+}
+
+fn hex4(input: &[u8]) -> Option<u16> {
+    let text = str::from_utf8(input.get(..4)?).ok()?;
+    match text.bytes().all(|b| b.is_ascii_hexdigit()) {
+        true => u16::from_str_radix(text, 16).ok(),
+        false => None,
+    }
+}
+
+fn string(input: &[u8]) -> Parsed<'_, Cow<'_, str>> {
+    let body = input.strip_prefix(b"\"").ok_or(input)?;
+    let len = body
+        .iter()
+        .position(|&b| b == b'"' || b == b'\\' || b < 0x20)
+        .unwrap_or(body.len());
+    match body.get(len) {
+        Some(b'"') => {
+            let text = str::from_utf8(&body[..len]).map_err(|e| &body[e.valid_up_to()..])?;
+            return Ok((&body[len + 1..], Cow::Borrowed(text)));
+        }
+        Some(b'\\') => {}
+        _ => return Err(&body[len..]),
+    }
+    let mut out = Vec::with_capacity(len + 16);
+    let mut rest = body;
+    loop {
+        let (&b, next) = rest.split_first().ok_or(rest)?;
+        match b {
+            b'"' => break,
+            b'\\' => {
+                let (&escape, next) = next.split_first().ok_or(next)?;
+                let c = match escape {
+                    b'"' => '"',
+                    b'\\' => '\\',
+                    b'/' => '/',
+                    b'b' => '\u{8}',
+                    b'f' => '\u{c}',
+                    b'n' => '\n',
+                    b'r' => '\r',
+                    b't' => '\t',
+                    b'u' => {
+                        let high = hex4(next).ok_or(rest)?;
+                        let (c, len) = match high {
+                            0xd800..=0xdbff => {
+                                let low = next
+                                    .get(4..6)
+                                    .filter(|x| x == b"\\u")
+                                    .and_then(|_| hex4(&next[6..]))
+                                    .filter(|x| (0xdc00..=0xdfff).contains(x))
+                                    .ok_or(rest)?;
+                                let c = 0x10000
+                                    + ((high as u32 - 0xd800) << 10)
+                                    + (low as u32 - 0xdc00);
+                                (char::from_u32(c), 10)
+                            }
+                            _ => (char::from_u32(high as u32), 4),
+                        };
+                        let c = c.ok_or(rest)?;
+                        out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                        rest = &next[len..];
+                        continue;
+                    }
+                    _ => return Err(rest),
+                };
+                out.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+                rest = next;
+            }
+            0x00..=0x1f => return Err(rest),
+            _ => {
+                out.push(b);
+                rest = next;
+            }
+        }
+    }
+    match String::from_utf8(out) {
+        Ok(x) => Ok((&rest[1..], Cow::Owned(x))),
+        Err(_) => Err(body),
+    }
+}
+
+fn value_at(input: &[u8], depth: usize) -> Parsed<'_, Value<'_>> {
+    let input = skip_whitespace(input);
+    let (rest, value) = match input.first() {
+        Some(b'n') if input.starts_with(b"null") => (&input[4..], Value::Null),
+        Some(b't') if input.starts_with(b"true") => (&input[4..], Value::Bool(true)),
+        Some(b'f') if input.starts_with(b"false") => (&input[5..], Value::Bool(false)),
+        Some(b'"') => {
+            let (rest, x) = string(input)?;
+            (rest, Value::String(x))
+        }
+        Some(b'[') if depth < MAX_DEPTH => {
+            let mut items = Vec::new();
+            let mut rest = skip_whitespace(&input[1..]);
+            if let Some(x) = rest.strip_prefix(b"]") {
+                return Ok((x, Value::Array(items)));
+            }
+            loop {
+                let (next, item) = value_at(rest, depth + 1)?;
+                items.push(item);
+                let next = skip_whitespace(next);
+                match next.first() {
+                    Some(b',') => rest = &next[1..],
+                    Some(b']') => break (&next[1..], Value::Array(items)),
+                    _ => return Err(next),
+                }
+            }
+        }
+        Some(b'{') if depth < MAX_DEPTH => {
+            let mut members = Vec::new();
+            let mut rest = skip_whitespace(&input[1..]);
+            if let Some(x) = rest.strip_prefix(b"}") {
+                return Ok((x, Value::Object(members)));
+            }
+            loop {
+                let (next, key) = string(skip_whitespace(rest))?;
+                let next = skip_whitespace(next);
+                let next = next.strip_prefix(b":").ok_or(next)?;
+                let (next, item) = value_at(next, depth + 1)?;
+                members.push((key, item));
+                let next = skip_whitespace(next);
+                match next.first() {
+                    Some(b',') => rest = &next[1..],
+                    Some(b'}') => break (&next[1..], Value::Object(members)),
+                    _ => return Err(next),
+                }
+            }
+        }
+        Some(b'-' | b'0'..=b'9') => {
+            let (rest, x) = number(input)?;
+            (rest, Value::Number(x))
+        }
+        _ => return Err(input),
+    };
+    Ok((rest, value))
+}
+
+/// Parse a JSON value, skipping whitespace before it.
+pub fn value<'a, Error: From<JsonError<'a>>>(input: &'a [u8]) -> Step<'a, Value<'a>, Error> {
+    match value_at(input, 0) {
+        Ok((rest, x)) => (rest, Ok(x)),
+        Err(at) => (input, Err(JsonError(at).into())),
+    }
+}
+
+std_error! {
+    JsonError<'a>;
+}
@@ -5,192 +5,2719 @@
 //! | Items | Description | Example |
 //! |---|---|---|
 //! | [`and`] | Combine two parsers where both must succeed. | `and(u16l, u32l)` |
-//! | [`or`] | Combine two parsers where at least one must succeed. | `or(u16l, u32l)` |
+//! | [`preceded`] | Run two parsers, outputting the second's output. | `preceded(tag("RIFF"), u32l)` |
+//! | [`terminated`] | Run two parsers, outputting the first's output. | `terminated(u32l, tag("\r\n"))` |
+//! | [`delimited`] | Run three parsers, outputting the middle one's output. | `delimited(tag("("), u32l, tag(")"))` |
+//! | [`separated_pair`] | Run three parsers, outputting the first's and last's outputs. | `separated_pair(key, tag("="), value)` |
+//! | [`tuple`](tuple()) | Combine up to 12 parsers where all must succeed. | `tuple((u16l, u32l, byte))` |
+//! | [`or`] | Try two parsers, outputting whichever succeeds first as an [`Either`]. | `or(u16l, u32l)` |
+//! | [`cut`] | Stop [`or`] and [`alt`] from trying other branches if a parser fails. | `alt((preceded(tag("{"), cut(object)), array))` |
+//! | [`alt`] | Try up to 12 parsers in order, outputting the first success. | `alt((tag("RIFF"), tag("RIFX")))` |
+//! | [`byte_map`] | Map a byte to an output through a table. | `byte_map(OPCODES)` |
+//! | [`satisfy`] | Parse a byte that matches a predicate. | `satisfy(\|b\| b.is_ascii_hexdigit())` |
+//! | [`one_of`] | Parse a byte that is in a set. | `one_of(b"+-")` |
+//! | [`none_of`] | Parse a byte that isn't in a set. | `none_of(b"\r\n")` |
+//! | [`enum_from`] | Parse a discriminant, converting it into an enum. | `enum_from::<Opcode, _, _, _>(u16l)` |
+//! | [`flags`] | Parse bit flags, checking them against the known flags. | `flags(0b111, UnknownBits::Reject, u16l)` |
+//! | [`flags_from`] | Parse bit flags into a `bitflags` type. **Requires the `bitflags` feature** | `flags_from::<Mode, _, _>(UnknownBits::Truncate, u32l)` |
+//! | [`boolean`] | Parse a byte as a boolean. | `boolean(Truthiness::Strict)` |
+//! | [`bool_u32l`] | Parse a 32-bit little-endian integer as a boolean. | `bool_u32l(Truthiness::Lenient)` |
+//! | [`bool_u32b`] | Parse a 32-bit big-endian integer as a boolean. | `bool_u32b(Truthiness::Strict)` |
 //! | [`take`] | Take N bytes. | `take(42)` |
+//! | [`skip_bytes`] | Skip N bytes. | `skip_bytes(4)` |
+//! | [`rest`] | Take the rest of the input. | `and(header, rest)` |
+//! | [`rest_len`] | Output how many bytes are left without consuming them. | `rest_len` |
+//! | [`take_while`] | Take bytes while they match a predicate. | `take_while(\|b\| b.is_ascii_digit())` |
+//! | [`take_while1`] | Take at least one byte while they match a predicate. | `take_while1(\|b\| b.is_ascii_alphabetic())` |
+//! | [`take_until`] | Take bytes up to a sequence of bytes. | `take_until(b"\r\n")` |
+//! | [`utf8`] | Take N bytes of UTF-8 text. | `utf8(16)` |
+//! | [`utf8_until`] | Take UTF-8 text up to a sequence of bytes. | `utf8_until(b"\r\n")` |
+//! | [`utf16l`] | Take N code units of UTF-16 little-endian text. | `and_then(u16l, \|len\| utf16l(len.into()))` |
+//! | [`utf16b`] | Take N code units of UTF-16 big-endian text. | `utf16b(32)` |
+//! | [`cstr_utf16l`] | Take UTF-16 little-endian text up to a null terminator. | `cstr_utf16l(260)` |
+//! | [`cstr_utf16b`] | Take UTF-16 big-endian text up to a null terminator. | `cstr_utf16b(260)` |
+//! | [`take_till`] | Take bytes up to one that matches a predicate. | `take_till(Terminator::Leave, \|b\| b == b' ')` |
+//! | [`take_till_byte`] | Take bytes up to a terminating byte. | `take_till_byte(0, Terminator::Consume)` |
+//! | [`cstr`] | Take bytes up to a null terminator. | `cstr(256)` |
+//! | [`cstr_utf8`] | Take UTF-8 text up to a null terminator. | `cstr_utf8(256)` |
+//! | [`length_data`] | Parse a length, then take that many bytes. | `length_data(u16b)` |
+//! | [`pstring`] | Parse a length, then take that many bytes of UTF-8 text. | `pstring(byte)` |
+//! | [`take_unstuffed`] | Take the rest of the input, removing byte stuffing. | `take_unstuffed(0x7d, \|b\| Some(b ^ 0x20))` |
 //! | [`seq`] | Run a parser N times in sequence. | `seq(u32l, 42)` |
+//! | [`seq_array`] | Run a parser a constant N times, collecting into an array. | `seq_array::<4, _, _, _>(u32l)` |
+//! | [`fold_many`] | Run a parser N times, folding the outputs together. | `fold_many(42, u32l, \|\| 0, \|a, x\| a ^ x)` |
+//! | [`length_count`] | Parse a count, then run a parser that many times. | `length_count(u16b, u32l)` |
+//! | [`seq_small`] | Run a parser N times, collecting into a `SmallVec`. **Requires the `smallvec` feature** | `seq_small::<[u32; 8], _, _>(count, u32l)` |
+//! | [`zip_seq`] | Run two parsers N times each, zipping the outputs. | `zip_seq(42, u16l, u32l)` |
+//! | [`zip_seq3`] | Run three parsers N times each, zipping the outputs. | `zip_seq3(42, u16l, u32l, byte)` |
+//! | [`project`] | Run a parser N times, storing selected fields in separate `Vec`s. | `project(42, record, (\|r: &Row\| r.id, \|r: &Row\| r.price))` |
+//! | [`many0`] | Run a parser until it fails. | `many0(chunk)` |
+//! | [`many1`] | Run a parser until it fails, at least once. | `many1(chunk)` |
+//! | [`many_m_n`] | Run a parser between M and N times. | `many_m_n(1, 16, entry)` |
+//! | [`many_till`] | Run a parser until a terminator succeeds. | `many_till(record, tag("END\0"))` |
+//! | [`count_exact_fill`] | Run a parser until the input is exactly consumed. | `count_exact_fill(u32l)` |
+//! | [`separated_list0`] | Run a parser zero or more times, separated by another. | `separated_list0(tag(","), u32l)` |
+//! | [`separated_list1`] | Run a parser one or more times, separated by another. | `separated_list1(tag(","), u32l)` |
+//! | [`separated_list_trailing`] | Run a parser separated by another, allowing a trailing separator. | `separated_list_trailing(tag(","), u32l)` |
 //! | [`tag`] | Match a sequence of bytes. | `tag("hello")` |
+//! | [`tag_no_case`] | Match a sequence of bytes, ignoring ASCII case. | `tag_no_case(b"riff")` |
+//! | [`bom`] | Skip a byte order mark, outputting the encoding it gives. | `bom` |
 //! | [`opt`] | Allow a parser to fail. | `opt(tag("hello"))` |
+//! | [`opt_or_default`] | Allow a parser to fail, using the default value. | `opt_or_default(u32l)` |
+//! | [`unwrap_or`] | Allow a parser to fail, using the given value. | `unwrap_or(1, u32l)` |
+//! | [`cond`] | Run a parser only if a flag is set. | `cond(flags & 1 != 0, u32l)` |
 //! | [`pod`] | Transmute bytes into a type. **Requires the `bytemuck` feature** | `seq(pod::<MyType>, 4)` |
+//! | [`pod_copied`] | Copy bytes into a type, allowing unaligned input. **Requires the `bytemuck` feature** | `seq(pod_copied::<MyType>, 4)` |
+//! | [`base64`] | Decode N characters of base64 text. | `base64(44)` |
+//! | [`base64_until`] | Decode base64 text until a delimiter. | `base64_until(b'\n')` |
+//! | [`hex`] | Decode N characters of hex text. | `hex(64)` |
+//! | [`hex_bytes`] | Decode N bytes of hex text, from twice as many characters. | `hex_bytes(32)` |
+//! | [`hex_byte`] | Decode two characters of hex text as a byte. | `seq(hex_byte, 4)` |
+//! | [`intel_hex_record`] | Parse an Intel HEX record. | `intel_hex_record` |
+//! | [`s_record`] | Parse a Motorola S-record. | `s_record` |
+//! | [`str_encoded`] | Decode N bytes of text in a legacy encoding. **Requires the `encoding_rs` feature** | `str_encoded(16, SHIFT_JIS)` |
+//! | [`lz4_block`] | Decompress an LZ4 block and parse it. **Requires the `lz4` feature** | `lz4_block(len, size, seq(u32l, 42))` |
+//! | [`lz4_frame`] | Decompress an LZ4 frame and parse it. **Requires the `lz4` feature** | `lz4_frame(seq(u32l, 42))` |
+//! | [`hashed`] | Check a region against the digest stored after it. **Requires the `digest` feature** | `hashed(seq(u32l, 42), Sha256::new(), take(32))` |
 //! | [`finish`] | Ensure there is no bytes left | `finish(seq(u16l, 128))` |
+//! | [`finish_strict`] | Ensure there is no bytes left, reporting their offset. | `finish_strict(file, seq(u16l, 128))` |
+//! | [`finish_padded`] | Ensure there is no bytes left other than zero padding. | `finish_padded(file, seq(u16l, 128))` |
+//! | [`skip_until`] | Skip bytes until a parser succeeds. | `skip_until(tag("hello"))` |
+//! | [`skip_until_tag`] | Skip bytes until a sequence of bytes is found. | `skip_until_tag("hello")` |
+//! | [`spanned`] | Output the byte range consumed relative to a root buffer. | `spanned(file, u32l)` |
+//! | [`recognize`] | Output the bytes consumed by a parser. | `recognize(seq(16, u32l))` |
+//! | [`consumed`] | Output the bytes consumed by a parser along with its output. | `consumed(header)` |
+//! | [`parse_at`] | Run a parser at an offset of a root buffer. | `parse_at(file, 1024, u32l)` |
+//! | [`peek`] | Run a parser without consuming input. | `peek(u32b)` |
+//! | [`not`] | Succeed only if a parser fails, without consuming input. | `not(tag("END"))` |
+//! | [`lookahead`] | Run a parser on the next N bytes without consuming them. | `lookahead(8, tag("RIFF"))` |
+//! | [`limit`] | Fail if a parser consumes more than N bytes. | `limit(64, seq(u32l, count))` |
+//! | [`into`] | Convert the output and error with `Into`. | `into::<u64, MyError, _, U32LError>(u32l)` |
+//! | [`verify`] | Fail if the output doesn't satisfy a predicate. | `verify(byte, \|&v\| v <= 3)` |
+//! | [`and_then`] | Run a parser made from another parser's output. | `and_then(u16l, \|len\| take(len.into()))` |
+//! | [`value`] | Replace the output with a value. | `value(Kind::Riff, tag("RIFF"))` |
+//! | [`skip`] | Run a parser, discarding its output. | `skip(tag("RIFF"))` |
+//! | [`context`] | Label a parser's errors with what it was parsing. | `context("fmt header", fmt_header)` |
+//! | [`map_res`] | Convert the output with a function that can fail. | `map_res(byte, Opcode::try_from)` |
+//!
+//! ## Chaining
+//!
+//! Every parser implements the [`Parser`] trait, which has methods like [`Parser::map`] and
+//! [`Parser::and`] for writing `u16l.and(u32l).map(|(a, b)| a as u32 + b)` instead of nesting.
+//!
+//! ## Errors
+//!
+//! Each parser has its own error type, and combinators take any error that the errors of their
+//! children convert into. Every built-in error converts into [`ParseError`], which holds an
+//! [`ErrorKind`] and where the error happened, so `Step<'a, T, ParseError<'a>>` works for parsers
+//! that don't need a custom error type.
+//!
+//! Use [`context`] to label a parser's errors with what it was parsing. Labels stack up through
+//! nested parsers, so a [`ParseError`] can say `riff chunk: fmt header: not enough bytes for
+//! number`. Custom errors can be labeled by implementing [`ContextError`].
+//!
+//! Errors hold the input where they happened. For errors that implement [`Located`], use
+//! [`Located::offset_in`] with the original input to get the byte offset instead, or
+//! [`render_error`] to show the bytes around it as a hex dump.
+//!
+//! ## Iterating
+//!
+//! Use [`iterate`] to run a parser over the input as an [`Iterator`], for streaming records
+//! without collecting them into a `Vec`. [`Iterate::remaining`] gives what's left after.
+//!
+//! ## Interning
+//!
+//! An [`Interner`] deduplicates repeated outputs, such as path components, so they can be stored
+//! as a cheap [`Symbol`]. Use [`intern`] to intern a parser's output.
+//!
+//! ## Memoization
+//!
+//! A [`Memo`] caches a parser's outputs by position, for formats where offset tables point at the
+//! same region many times. Use [`memo`] to run a parser through the cache.
+//!
+//! ## Metrics
+//!
+//! Use [`measured`] to report attempts, failures, and bytes consumed by a labeled parser to a
+//! [`Metrics`] sink, like a [`StatsMap`]. Measuring into `()` does nothing.
+//!
+//! ## Formats
+//!
+//! | Module | Description |
+//! |---|---|
+//! | [`bencode`] | Bencode values. |
+//! | [`bitcoin`] | Bitcoin block headers and transactions. |
+//! | [`csv`] | CSV and other delimiter-separated values. |
+//! | [`http1`] | HTTP/1.x request lines, status lines, and header fields. |
+//! | [`ini`] | `key = value` and INI-style lines. |
+//! | `json` | JSON values. **Requires the `json` feature** |
+//! | [`mqtt`] | MQTT 3.1.1 and 5 control packets. |
+//! | [`pcap`] | PCAP and PCAP-NG capture files. |
+//! | [`tls`] | TLS records, handshake headers, and extensions. |
+//! | [`ws`] | WebSocket frames. |
+//!
+//! ## Reader
+//!
+//! A [`Reader`] runs parsers against a buffer in turn, keeping track of the position. Use
+//! [`Reader::checkpoint`] and [`Reader::rewind`] to try a parse and roll it back.
+//!
+//! ## Segmented input
+//!
+//! A [`Chain`] runs parsers against input split between several segments, like a list of
+//! network buffers, copying only the bytes around a boundary when a parser needs to cross it.
+//! With the `bytes` feature it also implements `bytes::Buf`.
+//!
+//! ## Streaming UTF-8
+//!
+//! A [`Utf8Validator`] validates UTF-8 that arrives in chunks, where a code point may be split
+//! between one chunk and the next.
+//!
+//! ## ASCII parsers
+//!
+//! | Items | Description | Example |
+//! |---|---|---|
+//! | [`ascii_digit1`] | Take one or more ASCII digits. | `ascii_digit1` |
+//! | [`ascii_alpha1`] | Take one or more ASCII letters. | `ascii_alpha1` |
+//! | [`space0`] | Take zero or more spaces and tabs. | `space0` |
+//! | [`space1`] | Take one or more spaces and tabs. | `space1` |
+//! | [`decimal_u64`] | Parse ASCII decimal digits into a `u64`. | `decimal_u64` |
+//! | [`octal_u64`] | Parse ASCII octal digits into a `u64`. | `octal_u64` |
+//! | [`hex_u64`] | Parse ASCII hex digits into a `u64`. | `hex_u64` |
+//! | [`line()`] | Take a line up to `\n` or `\r\n`, consuming the line ending. | `line` |
+//! | [`crlf`] | Match a `\r\n` line ending. | `crlf` |
+//! | [`newline`] | Match a `\n` line ending. | `newline` |
+//!
+//! ## Timestamp parsers
+//!
+//! | Items | Description | Example |
+//! |---|---|---|
+//! | [`iso_date`] | Parse an ISO 8601 date. | `iso_date` |
+//! | [`iso_timestamp`] | Parse an ISO 8601 or RFC 3339 timestamp. | `iso_timestamp` |
+//!
+//! ## URL parsers
+//!
+//! | Items | Description | Example |
+//! |---|---|---|
+//! | [`url`] | Parse a URL into its components. | `url` |
+//! | [`url_scheme`] | Parse a URL scheme and the `:` after it. | `url_scheme` |
+//! | [`url_authority`] | Parse a URL authority after `//`. | `opt(url_authority)` |
+//! | [`url_path`] | Parse a URL path. | `url_path` |
+//! | [`url_query`] | Parse a URL query after `?`. | `opt(url_query)` |
+//! | [`url_fragment`] | Parse a URL fragment after `#`. | `opt(url_fragment)` |
+//! | [`percent_decode`] | Take the rest of the input, decoding percent escapes. | `percent_decode` |
 //!
 //! ## Number parsers
 //!
-//! | | `u8` | `u16` | `u32` | `u64` | `u128` | `f32` | `f64` |
-//! |---|---|---|---|---|---|---|---|
-//! | **Little Endian** | [`byte`] | [`u16l`] | [`u32l`] | [`u64l`] | [`u128l`] | [`f32l`] | [`f64l`] |
-//! | **Big Endian** | [`byte`] | [`u16b`] | [`u32b`] | [`u64b`] | [`u128b`] | [`f32b`] | [`f64b`] |
+//! | | `u8` | `i8` | `u16` | `u32` | `u64` | `u128` | `f32` | `f64` |
+//! |---|---|---|---|---|---|---|---|---|
+//! | **Little Endian** | [`byte`] | [`sbyte`] | [`u16l`] | [`u32l`] | [`u64l`] | [`u128l`] | [`f32l`] | [`f64l`] |
+//! | **Big Endian** | [`byte`] | [`sbyte`] | [`u16b`] | [`u32b`] | [`u64b`] | [`u128b`] | [`f32b`] | [`f64b`] |
+//! | **Native Endian** | [`byte`] | [`sbyte`] | [`u16n`] | [`u32n`] | [`u64n`] | [`u128n`] | [`f32n`] | [`f64n`] |
+//! | **Runtime [`Endian`]** | [`byte`] | [`sbyte`] | [`u16e`] | [`u32e`] | [`u64e`] | [`u128e`] | [`f32e`] | [`f64e`] |
+//!
+//! | | `u24` | `i24` | `u48` | `f16` |
+//! |---|---|---|---|---|
+//! | **Little Endian** | [`u24l`] | [`i24l`] | [`u48l`] | [`f16l`] |
+//! | **Big Endian** | [`u24b`] | [`i24b`] | [`u48b`] | [`f16b`] |
+//!
+//! These output the next widest type, with signed integers sign extended.
+//!
+//! | | 16.16 fixed-point | 2.14 fixed-point |
+//! |---|---|---|
+//! | **Little Endian** | [`fixed16_16l`] | [`fixed2_14l`] |
+//! | **Big Endian** | [`fixed16_16b`] | [`fixed2_14b`] |
+//!
+//! These output an `f64` and an `f32`, which represent every fixed-point value exactly.
+//!
+//! To write a parser once for either byte order, make it generic over an [`Endianness`] and use
+//! [`num`] like `num::<u32, E, _>`, then pick [`LittleEndian`], [`BigEndian`], or
+//! [`NativeEndian`] when calling it. For byte orders only known at runtime, like from the magic
+//! number of a file, use [`num_endian`] or the parsers like [`u32e`] with an [`Endian`].
+//!
+//! ## Variable-length integers
+//!
+//! | Items | Description | Example |
+//! |---|---|---|
+//! | [`quic_varint`] | Parse a QUIC variable-length integer and its length. | `quic_varint` |
+//! | [`compact_size`] | Parse a Bitcoin CompactSize integer. | `compact_size` |
+//! | [`uleb128`] | Parse an unsigned LEB128 integer. | `uleb128` |
+//! | [`sleb128`] | Parse a signed LEB128 integer. | `sleb128` |
+//! | [`varint_u64`] | Parse a protobuf varint. | `varint_u64` |
+//! | [`zigzag_i64`] | Parse a protobuf zigzag-encoded signed varint. | `zigzag_i64` |
+//! | [`vlq_u32`] | Parse a MIDI variable-length quantity. | `vlq_u32` |
 //!
 //! ## Features
 //!
-//! - `bytemuck`: Enables the [`pod`] parser
+//! - `std`: Implements `std::error::Error` for the error types. Implies `alloc`
+//! - `alloc` (default): Enables the parsers that output a `Vec` or `Cow`, like [`seq`], and the
+//!   items that use them, like [`Memo`] and [`Chain`]. Without it the crate doesn't need an
+//!   allocator, and [`seq_array`] and [`fold_many`] can be used for repetition instead.
+//! - `bytemuck`: Enables the [`pod`] and [`pod_copied`] parsers
+//! - `smallvec`: Enables the [`seq_small`] parser
+//! - `lz4`: Enables the [`lz4_block`] and [`lz4_frame`] parsers
+//! - `encoding_rs`: Enables the [`str_encoded`] parser
+//! - `json`: Enables the [`json`] module
+//! - `digest`: Enables the [`hashed`] parser
+//! - `memchr`: Speeds up searching in [`take_until`], [`take_till_byte`], and [`skip_until_tag`]
+//! - `bytes`: Implements `bytes::Buf` for [`Chain`]
+//! - `bitflags`: Enables the [`flags_from`] parser
 //! ## MSRV
 //!
 //! Minimum supported Rust version is: 1.60
 //!
 
-#![no_std]
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::ops::{BitAnd, Not, Range};
+
+/// Run a parser, outputting the rest of the input and its output, or returning early with
+/// `$input` and its error converted.
+macro_rules! step {
+    ($input:expr, $parser:expr) => {
+        match $parser {
+            (rest, Ok(x)) => (rest, x),
+            (_, Err(e)) => return ($input, Err(e.into())),
+        }
+    };
+}
+
+/// Implement `std::error::Error` for error types. **Requires the `std` feature**
+macro_rules! std_error {
+    ($($name:ident $(<$($param:tt),*>)?;)*) => {
+        $(
+            #[cfg(feature = "std")]
+            impl$(<$($param),*>)? std::error::Error for $name$(<$($param),*>)?
+            where
+                Self: core::fmt::Debug + core::fmt::Display,
+            {
+            }
+        )*
+    };
+}
+
+mod alt;
+mod ascii;
+#[cfg(feature = "alloc")]
+mod base64;
+#[cfg(feature = "alloc")]
+pub mod bencode;
+#[cfg(feature = "alloc")]
+pub mod bitcoin;
+mod bom;
+#[cfg(feature = "alloc")]
+mod chain;
+#[cfg(feature = "alloc")]
+pub mod csv;
+#[cfg(feature = "encoding_rs")]
+mod encoding;
+mod endian;
+mod error;
+#[cfg(feature = "digest")]
+mod hashed;
+mod hex;
+pub mod http1;
+pub mod ini;
+#[cfg(feature = "alloc")]
+mod intern;
+mod iterate;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "lz4")]
+mod lz4;
+#[cfg(feature = "alloc")]
+mod memo;
+mod metrics;
+pub mod mqtt;
+mod parser;
+pub mod pcap;
+#[cfg(feature = "alloc")]
+mod project;
+mod reader;
+#[cfg(feature = "alloc")]
+mod render;
+mod timestamp;
+pub mod tls;
+mod tuple;
+mod url;
+mod utf16;
+mod utf8;
+mod varint;
+pub mod ws;
+
+pub use alt::{alt, Alt, AltError};
+pub use ascii::{ascii_alpha1, ascii_digit1, crlf, line, newline, space0, space1};
+pub use ascii::{decimal_u64, hex_u64, octal_u64, AsciiNumError};
+#[cfg(feature = "alloc")]
+pub use base64::{base64, base64_until, Base64Error};
+pub use bom::{bom, Bom};
+#[cfg(feature = "alloc")]
+pub use chain::Chain;
+#[cfg(feature = "encoding_rs")]
+pub use encoding::{str_encoded, EncodingError};
+pub use endian::{f32e, f64e, i128e, i16e, i32e, i64e, u128e, u16e, u32e, u64e};
+pub use endian::{num, num_endian, Endian, NumError, Number};
+pub use endian::{BigEndian, Endianness, LittleEndian, NativeEndian};
+pub use error::{Context, ContextError, ErrorKind, Located, ParseError, Recoverable};
+#[cfg(feature = "digest")]
+pub use hashed::{hashed, DigestError};
+#[cfg(feature = "alloc")]
+pub use hex::{hex, hex_bytes, intel_hex_record, s_record, IntelHexRecord, SRecord};
+pub use hex::{hex_byte, ChecksumError, HexError};
+#[cfg(feature = "alloc")]
+pub use intern::{intern, Interner, Symbol};
+pub use iterate::{iterate, Iterate};
+#[cfg(feature = "lz4")]
+pub use lz4::{lz4_block, lz4_frame, Lz4Error};
+#[cfg(feature = "alloc")]
+pub use memo::{memo, Memo};
+#[cfg(feature = "alloc")]
+pub use metrics::StatsMap;
+pub use metrics::{measured, Metrics, ParserStats};
+pub use parser::{And, Map, Opt, Or, Parser, ThenTake};
+#[cfg(feature = "alloc")]
+pub use project::{project, Extract};
+pub use reader::{Checkpoint, Reader};
+#[cfg(feature = "alloc")]
+pub use render::render_error;
+pub use timestamp::{iso_date, iso_timestamp, Date, Timestamp, TimestampError};
+pub use tuple::{tuple, Tuple, TupleError};
+#[cfg(feature = "alloc")]
+pub use url::percent_decode;
+pub use url::{
+    url, url_authority, url_fragment, url_path, url_query, url_scheme, PercentError, Url, UrlError,
+};
+pub use utf16::{cstr_utf16b, cstr_utf16l, utf16b, utf16l, Utf16, Utf16Error};
+pub use utf8::{Utf8Error, Utf8StreamError, Utf8Validator};
+pub use varint::{compact_size, quic_varint, CompactSizeError, QuicVarintError};
+pub use varint::{sleb128, uleb128, Leb128Error};
+pub use varint::{varint_u64, zigzag_i64, VarintError, VarintOverlongError};
+pub use varint::{vlq_u32, VlqError};
+
+pub type Step<'a, Output, Error> = (&'a [u8], Result<Output, Error>);
+
+/// Debugs input as hex, only showing the first few bytes.
+pub(crate) struct InputDebug<'a>(pub(crate) &'a [u8]);
+
+impl<'a> Debug for InputDebug<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        const SHOWN: usize = 16;
+        write!(fmt, "[")?;
+        for (i, b) in self.0.iter().take(SHOWN).enumerate() {
+            match i {
+                0 => write!(fmt, "{:02x}", b)?,
+                _ => write!(fmt, " {:02x}", b)?,
+            }
+        }
+        if self.0.len() > SHOWN {
+            write!(fmt, " ..")?;
+        }
+        write!(fmt, "] ({} bytes)", self.0.len())
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ByteError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+}
+
+impl<'a> Debug for ByteError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ByteError")
+            .field("at", &InputDebug(self.at))
+            .finish()
+    }
+}
+
+impl<'a> Display for ByteError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "expected a byte")
+    }
+}
+
+pub fn byte<'a, Error: From<ByteError<'a>>>(input: &'a [u8]) -> Step<'a, u8, Error> {
+    match input.split_first() {
+        Some((&byte, rest)) => (rest, Ok(byte)),
+        None => (input, Err(ByteError { at: input }.into())),
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ByteMapError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The byte that had no entry in the table
+    pub byte: u8,
+}
+
+impl<'a> Debug for ByteMapError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ByteMapError")
+            .field("at", &InputDebug(self.at))
+            .field("byte", &self.byte)
+            .finish()
+    }
+}
+
+impl<'a> Display for ByteMapError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "byte {:#04x} has no entry in the table", self.byte)
+    }
+}
+
+/// Parse a byte, mapping it to an output through a table.
+pub fn byte_map<'a, Output: Clone, Error: From<ByteError<'a>> + From<ByteMapError<'a>>>(
+    table: [Option<Output>; 256],
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| match byte::<ByteError>(input) {
+        (rest, Ok(byte)) => match &table[byte as usize] {
+            Some(x) => (rest, Ok(x.clone())),
+            None => (input, Err(ByteMapError { at: input, byte }.into())),
+        },
+        (_, Err(e)) => (input, Err(e.into())),
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct SatisfyError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The byte that didn't match the predicate
+    pub byte: u8,
+}
+
+impl<'a> Debug for SatisfyError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("SatisfyError")
+            .field("at", &InputDebug(self.at))
+            .field("byte", &self.byte)
+            .finish()
+    }
+}
+
+impl<'a> Display for SatisfyError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "byte {:#04x} didn't match the predicate", self.byte)
+    }
+}
+
+/// Parse a byte that matches a predicate.
+pub fn satisfy<'a, Error: From<ByteError<'a>> + From<SatisfyError<'a>>>(
+    predicate: impl Fn(u8) -> bool,
+) -> impl Fn(&'a [u8]) -> Step<'a, u8, Error> {
+    move |input| match byte::<ByteError>(input) {
+        (rest, Ok(byte)) if (predicate)(byte) => (rest, Ok(byte)),
+        (_, Ok(byte)) => (input, Err(SatisfyError { at: input, byte }.into())),
+        (_, Err(e)) => (input, Err(e.into())),
+    }
+}
+
+/// Parse a byte that is in a set of bytes.
+pub fn one_of<'a, 'b, Error: From<ByteError<'a>> + From<SatisfyError<'a>>>(
+    set: &'b [u8],
+) -> impl Fn(&'a [u8]) -> Step<'a, u8, Error> + 'b {
+    move |input| satisfy(|b| set.contains(&b))(input)
+}
+
+/// Parse a byte that isn't in a set of bytes.
+pub fn none_of<'a, 'b, Error: From<ByteError<'a>> + From<SatisfyError<'a>>>(
+    set: &'b [u8],
+) -> impl Fn(&'a [u8]) -> Step<'a, u8, Error> + 'b {
+    move |input| satisfy(|b| !set.contains(&b))(input)
+}
+
+#[derive(Clone, PartialEq)]
+pub struct EnumError<'a, Discriminant> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The discriminant that didn't match a variant
+    pub value: Discriminant,
+}
+
+impl<'a, Discriminant: Debug> Debug for EnumError<'a, Discriminant> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("EnumError")
+            .field("at", &InputDebug(self.at))
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<'a, Discriminant: Display> Display for EnumError<'a, Discriminant> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "unknown discriminant {}", self.value)
+    }
+}
+
+/// Parse a discriminant, converting it into an enum with `TryFrom`.
+pub fn enum_from<'a, Output, Error, Discriminant, ChildError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Discriminant, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error>
+where
+    Output: TryFrom<Discriminant>,
+    Discriminant: Copy,
+    Error: From<ChildError> + From<EnumError<'a, Discriminant>>,
+{
+    move |input| {
+        let (rest, value) = step!(input, (child)(input));
+        match Output::try_from(value) {
+            Ok(x) => (rest, Ok(x)),
+            Err(_) => (input, Err(EnumError { at: input, value }.into())),
+        }
+    }
+}
+
+/// What to do with bits that aren't known flags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnknownBits {
+    /// Fail with a [`FlagsError`]
+    Reject,
+    /// Clear the unknown bits
+    Truncate,
+    /// Keep the unknown bits
+    Keep,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct FlagsError<'a, Bits> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The bits that weren't known flags
+    pub unknown: Bits,
+}
+
+impl<'a, Bits: Debug> Debug for FlagsError<'a, Bits> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("FlagsError")
+            .field("at", &InputDebug(self.at))
+            .field("unknown", &self.unknown)
+            .finish()
+    }
+}
+
+impl<'a, Bits: fmt::LowerHex> Display for FlagsError<'a, Bits> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "unknown flags {:#x}", self.unknown)
+    }
+}
+
+/// Parse a number of bit flags, checking it against a mask of the known flags.
+pub fn flags<'a, Bits, Error, ChildError>(
+    known: Bits,
+    unknown_bits: UnknownBits,
+    child: impl Fn(&'a [u8]) -> Step<'a, Bits, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Bits, Error>
+where
+    Bits: Copy + Default + PartialEq + BitAnd<Output = Bits> + Not<Output = Bits>,
+    Error: From<ChildError> + From<FlagsError<'a, Bits>>,
+{
+    move |input| {
+        let (rest, bits) = step!(input, (child)(input));
+        let unknown = bits & !known;
+        match unknown_bits {
+            UnknownBits::Reject if unknown != Bits::default() => {
+                (input, Err(FlagsError { at: input, unknown }.into()))
+            }
+            UnknownBits::Truncate => (rest, Ok(bits & known)),
+            _ => (rest, Ok(bits)),
+        }
+    }
+}
+
+/// Parse a number of bit flags into a type made with the `bitflags` crate. **Requires the
+/// `bitflags` feature**
+#[cfg(feature = "bitflags")]
+pub fn flags_from<'a, Output, Error, ChildError>(
+    unknown_bits: UnknownBits,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output::Bits, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error>
+where
+    Output: bitflags::Flags,
+    Error: From<ChildError> + From<FlagsError<'a, Output::Bits>>,
+{
+    move |input| {
+        let (rest, bits) = step!(input, (child)(input));
+        match unknown_bits {
+            UnknownBits::Reject => match Output::from_bits(bits) {
+                Some(x) => (rest, Ok(x)),
+                None => {
+                    let unknown = bits & !Output::all().bits();
+                    (input, Err(FlagsError { at: input, unknown }.into()))
+                }
+            },
+            UnknownBits::Truncate => (rest, Ok(Output::from_bits_truncate(bits))),
+            UnknownBits::Keep => (rest, Ok(Output::from_bits_retain(bits))),
+        }
+    }
+}
+
+/// Which values parse as booleans.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Truthiness {
+    /// Only 0 is false and 1 is true
+    Strict,
+    /// 0 is false and anything else is true
+    Lenient,
+}
+
+impl Truthiness {
+    fn get(self, value: u32) -> Option<bool> {
+        match (self, value) {
+            (_, 0) => Some(false),
+            (_, 1) | (Self::Lenient, _) => Some(true),
+            (Self::Strict, _) => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct BoolError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The value that wasn't a boolean
+    pub value: u32,
+}
+
+impl<'a> Debug for BoolError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("BoolError")
+            .field("at", &InputDebug(self.at))
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<'a> Display for BoolError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{} isn't a boolean", self.value)
+    }
+}
+
+/// Parse a byte as a boolean.
+pub fn boolean<'a, Error: From<ByteError<'a>> + From<BoolError<'a>>>(
+    truthiness: Truthiness,
+) -> impl Fn(&'a [u8]) -> Step<'a, bool, Error> {
+    move |input| {
+        let (rest, value) = step!(input, byte::<ByteError>(input));
+        match truthiness.get(value.into()) {
+            Some(x) => (rest, Ok(x)),
+            None => (
+                input,
+                Err(BoolError {
+                    at: input,
+                    value: value.into(),
+                }
+                .into()),
+            ),
+        }
+    }
+}
+
+/// Parse a 32-bit little-endian integer as a boolean.
+pub fn bool_u32l<'a, Error: From<U32LError<'a>> + From<BoolError<'a>>>(
+    truthiness: Truthiness,
+) -> impl Fn(&'a [u8]) -> Step<'a, bool, Error> {
+    move |input| {
+        let (rest, value) = step!(input, u32l::<U32LError>(input));
+        match truthiness.get(value) {
+            Some(x) => (rest, Ok(x)),
+            None => (input, Err(BoolError { at: input, value }.into())),
+        }
+    }
+}
+
+/// Parse a 32-bit big-endian integer as a boolean.
+pub fn bool_u32b<'a, Error: From<U32BError<'a>> + From<BoolError<'a>>>(
+    truthiness: Truthiness,
+) -> impl Fn(&'a [u8]) -> Step<'a, bool, Error> {
+    move |input| {
+        let (rest, value) = step!(input, u32b::<U32BError>(input));
+        match truthiness.get(value) {
+            Some(x) => (rest, Ok(x)),
+            None => (input, Err(BoolError { at: input, value }.into())),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct TakeError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for TakeError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("TakeError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for TakeError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "not enough bytes, {} left", self.0.len())
+    }
+}
+
+pub fn take<'a, Error: From<TakeError<'a>>>(
+    count: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], Error> {
+    move |input| match input.len() < count {
+        true => (input, Err(TakeError(input).into())),
+        false => {
+            let (out, rest) = input.split_at(count);
+            (rest, Ok(out))
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum LengthDataError<'a, LengthError> {
+    /// The length parser's error
+    Length(LengthError),
+    /// There were less bytes left than the length
+    Data {
+        /// Where the error happened
+        at: &'a [u8],
+        length: usize,
+    },
+}
+
+impl<'a, LengthError: Debug> Debug for LengthDataError<'a, LengthError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length(e) => fmt.debug_tuple("Length").field(e).finish(),
+            Self::Data { at, length } => fmt
+                .debug_struct("Data")
+                .field("at", &InputDebug(at))
+                .field("length", length)
+                .finish(),
+        }
+    }
+}
+
+impl<'a, LengthError: Display> Display for LengthDataError<'a, LengthError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length(e) => write!(fmt, "invalid length: {}", e),
+            Self::Data { at, length } => {
+                write!(fmt, "expected {} bytes, {} left", length, at.len())
+            }
+        }
+    }
+}
+
+/// Parse a length, then take that many bytes.
+pub fn length_data<'a, Length, Error, LengthError>(
+    length: impl Fn(&'a [u8]) -> Step<'a, Length, LengthError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], Error>
+where
+    Length: TryInto<usize>,
+    Error: From<LengthDataError<'a, LengthError>>,
+{
+    move |input| {
+        let (rest, length) = match (length)(input) {
+            (rest, Ok(x)) => (rest, x.try_into().unwrap_or(usize::MAX)),
+            (_, Err(e)) => return (input, Err(LengthDataError::Length(e).into())),
+        };
+        match take::<TakeError>(length)(rest) {
+            (rest, Ok(x)) => (rest, Ok(x)),
+            (_, Err(TakeError(at))) => (input, Err(LengthDataError::Data { at, length }.into())),
+        }
+    }
+}
+
+/// Parse a length, then take that many bytes of UTF-8 text, like a Pascal string. Use
+/// [`length_data`] for strings that aren't UTF-8.
+pub fn pstring<'a, Length, Error, LengthError>(
+    length: impl Fn(&'a [u8]) -> Step<'a, Length, LengthError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a str, Error>
+where
+    Length: TryInto<usize>,
+    Error: From<LengthDataError<'a, LengthError>> + From<Utf8Error<'a>>,
+{
+    let data = length_data::<_, LengthDataError<'a, LengthError>, _>(length);
+    move |input| {
+        let (rest, out) = step!(input, (data)(input));
+        match utf8::to_str(out) {
+            Ok(x) => (rest, Ok(x)),
+            Err(e) => (input, Err(e.into())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TakeWhileError {}
+
+impl Display for TakeWhileError {
+    fn fmt(&self, _fmt: &mut Formatter<'_>) -> fmt::Result {
+        match *self {}
+    }
+}
+
+/// Take the longest run of bytes that match a predicate.
+pub fn take_while<'a>(
+    predicate: impl Fn(u8) -> bool,
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], TakeWhileError> {
+    move |input| {
+        let count = input.iter().take_while(|&&b| (predicate)(b)).count();
+        let (out, rest) = input.split_at(count);
+        (rest, Ok(out))
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct TakeWhile1Error<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for TakeWhile1Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("TakeWhile1Error")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for TakeWhile1Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "expected at least one matching byte")
+    }
+}
+
+/// Take the longest run of bytes that match a predicate, failing if there isn't at least one.
+pub fn take_while1<'a, Error: From<TakeWhile1Error<'a>>>(
+    predicate: impl Fn(u8) -> bool,
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], Error> {
+    let child = take_while(predicate);
+    move |input| match (child)(input) {
+        (_, Ok([])) => (input, Err(TakeWhile1Error(input).into())),
+        (rest, Ok(x)) => (rest, Ok(x)),
+        (_, Err(e)) => match e {},
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct TakeUntilError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for TakeUntilError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("TakeUntilError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for TakeUntilError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "sequence of bytes not found")
+    }
+}
+
+/// Position of the first occurrence of `needle` in `haystack`.
+#[cfg(feature = "memchr")]
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    memchr::memmem::find(haystack, needle)
+}
+
+/// Position of the first occurrence of `needle` in `haystack`.
+#[cfg(not(feature = "memchr"))]
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    let first = match needle.first() {
+        Some(x) => x,
+        None => return Some(0),
+    };
+    let mut at = 0;
+    while let Some(i) = haystack[at..].iter().position(|b| b == first) {
+        at += i;
+        if haystack[at..].starts_with(needle) {
+            return Some(at);
+        }
+        at += 1;
+    }
+    None
+}
+
+/// Take bytes up to a sequence of bytes, which is not consumed. Searching is faster with the
+/// `memchr` feature.
+pub fn take_until<'a, 'b, Error: From<TakeUntilError<'a>>>(
+    needle: &'b [u8],
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], Error> + 'b {
+    move |input| match find(input, needle) {
+        Some(i) => {
+            let (out, rest) = input.split_at(i);
+            (rest, Ok(out))
+        }
+        None => (input, Err(TakeUntilError(input).into())),
+    }
+}
+
+/// Take N bytes of UTF-8 text.
+pub fn utf8<'a, Error: From<TakeError<'a>> + From<Utf8Error<'a>>>(
+    count: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a str, Error> {
+    move |input| {
+        let (rest, out) = step!(input, take::<TakeError>(count)(input));
+        match utf8::to_str(out) {
+            Ok(x) => (rest, Ok(x)),
+            Err(e) => (input, Err(e.into())),
+        }
+    }
+}
+
+/// Take UTF-8 text up to a sequence of bytes, which is not consumed. See [`take_until`].
+pub fn utf8_until<'a, 'b, Error: From<TakeUntilError<'a>> + From<Utf8Error<'a>>>(
+    terminator: &'b [u8],
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a str, Error> + 'b {
+    move |input| {
+        let (rest, out) = step!(input, take_until::<TakeUntilError>(terminator)(input));
+        match utf8::to_str(out) {
+            Ok(x) => (rest, Ok(x)),
+            Err(e) => (input, Err(e.into())),
+        }
+    }
+}
+
+/// Whether a terminator is consumed along with the bytes before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Terminator {
+    Consume,
+    Leave,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct TakeTillError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for TakeTillError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("TakeTillError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for TakeTillError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "terminator not found")
+    }
+}
+
+/// Split off the bytes before a terminator at `i`, skipping the terminator if it's consumed.
+fn split_terminated(input: &[u8], i: usize, terminator: Terminator) -> (&[u8], &[u8]) {
+    let (out, rest) = input.split_at(i);
+    match terminator {
+        Terminator::Consume => (out, &rest[1..]),
+        Terminator::Leave => (out, rest),
+    }
+}
+
+/// Take bytes up to the first byte that matches a predicate.
+pub fn take_till<'a, Error: From<TakeTillError<'a>>>(
+    terminator: Terminator,
+    predicate: impl Fn(u8) -> bool,
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], Error> {
+    move |input| match input.iter().position(|&b| (predicate)(b)) {
+        Some(i) => {
+            let (out, rest) = split_terminated(input, i, terminator);
+            (rest, Ok(out))
+        }
+        None => (input, Err(TakeTillError(input).into())),
+    }
+}
+
+/// Take bytes up to the first occurrence of a byte, like a null terminator. Searching is faster
+/// with the `memchr` feature.
+pub fn take_till_byte<'a, Error: From<TakeTillError<'a>>>(
+    byte: u8,
+    terminator: Terminator,
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], Error> {
+    move |input| {
+        #[cfg(feature = "memchr")]
+        let position = memchr::memchr(byte, input);
+        #[cfg(not(feature = "memchr"))]
+        let position = input.iter().position(|&b| b == byte);
+        match position {
+            Some(i) => {
+                let (out, rest) = split_terminated(input, i, terminator);
+                (rest, Ok(out))
+            }
+            None => (input, Err(TakeTillError(input).into())),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct CStrError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The most bytes, or code units for UTF-16, that were searched for a null terminator
+    pub max_len: usize,
+}
+
+impl<'a> Debug for CStrError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("CStrError")
+            .field("at", &InputDebug(self.at))
+            .field("max_len", &self.max_len)
+            .finish()
+    }
+}
+
+impl<'a> Display for CStrError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "no null terminator in {} bytes", self.max_len)
+    }
+}
+
+/// Take bytes up to a null terminator, consuming the terminator. Fails if there's no terminator
+/// in the first `max_len` bytes, so corrupt input isn't scanned to the end.
+pub fn cstr<'a, Error: From<CStrError<'a>>>(
+    max_len: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], Error> {
+    move |input| {
+        let window = &input[..input.len().min(max_len)];
+        match take_till_byte::<TakeTillError>(0, Terminator::Consume)(window) {
+            (_, Ok(out)) => (&input[out.len() + 1..], Ok(out)),
+            (_, Err(_)) => (input, Err(CStrError { at: input, max_len }.into())),
+        }
+    }
+}
+
+/// Take UTF-8 text up to a null terminator, consuming the terminator. See [`cstr`].
+pub fn cstr_utf8<'a, Error: From<CStrError<'a>> + From<Utf8Error<'a>>>(
+    max_len: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a str, Error> {
+    move |input| {
+        let (rest, out) = step!(input, cstr::<CStrError>(max_len)(input));
+        match utf8::to_str(out) {
+            Ok(x) => (rest, Ok(x)),
+            Err(e) => (input, Err(e.into())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RestError {}
+
+impl Display for RestError {
+    fn fmt(&self, _fmt: &mut Formatter<'_>) -> fmt::Result {
+        match *self {}
+    }
+}
+
+/// Take the rest of the input.
+pub fn rest<'a>(input: &'a [u8]) -> Step<'a, &'a [u8], RestError> {
+    (&input[input.len()..], Ok(input))
+}
+
+/// Output how many bytes are left without consuming them.
+pub fn rest_len<'a>(input: &'a [u8]) -> Step<'a, usize, RestError> {
+    (input, Ok(input.len()))
+}
+
+/// Skip N bytes. Like [`take`], but outputs nothing.
+pub fn skip_bytes<'a, Error: From<TakeError<'a>>>(
+    count: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, (), Error> {
+    move |input| match input.get(count..) {
+        Some(rest) => (rest, Ok(())),
+        None => (input, Err(TakeError(input).into())),
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct UnstuffError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for UnstuffError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("UnstuffError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for UnstuffError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid escape at {} bytes from the end", self.0.len())
+    }
+}
+
+/// Take the rest of the input, replacing each `escape` byte and the byte after it with the
+/// output of `map`. The output is only copied when there is something to replace.
+#[cfg(feature = "alloc")]
+pub fn take_unstuffed<'a, Error: From<UnstuffError<'a>>>(
+    escape: u8,
+    map: impl Fn(u8) -> Option<u8>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Cow<'a, [u8]>, Error> {
+    move |input| {
+        let first = match input.iter().position(|&b| b == escape) {
+            Some(x) => x,
+            None => return (&input[input.len()..], Ok(Cow::Borrowed(input))),
+        };
+        let mut out = Vec::with_capacity(input.len());
+        out.extend_from_slice(&input[..first]);
+        let mut bytes = input[first..].iter().enumerate();
+        while let Some((i, &b)) = bytes.next() {
+            if b != escape {
+                out.push(b);
+                continue;
+            }
+            match bytes.next().and_then(|(_, &b)| (map)(b)) {
+                Some(x) => out.push(x),
+                None => return (input, Err(UnstuffError(&input[first + i..]).into())),
+            }
+        }
+        (&input[input.len()..], Ok(Cow::Owned(out)))
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct SeqError<'a, ChildError> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// What iteration the error happened
+    pub step: usize,
+    /// The child parser's error
+    pub child_error: ChildError,
+}
+
+impl<'a, ChildError: Display> Display for SeqError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "step {}: {}", self.step, self.child_error)
+    }
+}
+
+impl<'a, ChildError: Debug> Debug for SeqError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("SeqError")
+            .field("at", &InputDebug(self.at))
+            .field("step", &self.step)
+            .field("child_error", &self.child_error)
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+pub fn seq<'a, Output, Error: From<SeqError<'a, ChildError>>, ChildError>(
+    count: usize,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<Output>, Error> {
+    move |mut input| {
+        let before = input;
+        // The count may come from the input, so don't trust it further than the bytes left
+        let mut out = Vec::with_capacity(count.min(input.len()));
+        for step in 0..count {
+            let (rest, result) = (child)(input);
+            match result {
+                Ok(x) => out.push(x),
+                Err(child_error) => {
+                    return (
+                        before,
+                        Err(SeqError {
+                            at: input,
+                            step,
+                            child_error,
+                        }
+                        .into()),
+                    )
+                }
+            }
+            input = rest;
+        }
+        (input, Ok(out))
+    }
+}
+
+/// Run a parser N times in sequence, folding the outputs into an accumulator instead of
+/// collecting them.
+pub fn fold_many<'a, Output, Acc, Error: From<SeqError<'a, ChildError>>, ChildError>(
+    count: usize,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+    init: impl Fn() -> Acc,
+    fold: impl Fn(Acc, Output) -> Acc,
+) -> impl Fn(&'a [u8]) -> Step<'a, Acc, Error> {
+    move |mut input| {
+        let before = input;
+        let mut acc = (init)();
+        for step in 0..count {
+            match (child)(input) {
+                (rest, Ok(x)) => {
+                    acc = (fold)(acc, x);
+                    input = rest;
+                }
+                (_, Err(child_error)) => {
+                    return (
+                        before,
+                        Err(SeqError {
+                            at: input,
+                            step,
+                            child_error,
+                        }
+                        .into()),
+                    )
+                }
+            }
+        }
+        (input, Ok(acc))
+    }
+}
+
+/// Run a parser a constant N times in sequence, collecting into an array.
+pub fn seq_array<'a, const N: usize, Output, Error: From<SeqError<'a, ChildError>>, ChildError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, [Output; N], Error> {
+    move |input| {
+        let mut at = input;
+        let mut step = 0;
+        let mut error = None;
+        let out = [(); N].map(|_| {
+            if error.is_some() {
+                return None;
+            }
+            match (child)(at) {
+                (rest, Ok(x)) => {
+                    at = rest;
+                    step += 1;
+                    Some(x)
+                }
+                (_, Err(child_error)) => {
+                    error = Some(SeqError {
+                        at,
+                        step,
+                        child_error,
+                    });
+                    None
+                }
+            }
+        });
+        match error {
+            Some(e) => (input, Err(e.into())),
+            None => (at, Ok(out.map(|x| x.expect("every step succeeded")))),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum LengthCountError<'a, LengthError, ChildError> {
+    /// The count parser's error
+    Length(LengthError),
+    /// The child parser's error
+    Child(SeqError<'a, ChildError>),
+}
+
+impl<'a, LengthError: Display, ChildError: Display> Display
+    for LengthCountError<'a, LengthError, ChildError>
+{
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Length(e) => write!(fmt, "invalid count: {}", e),
+            Self::Child(e) => e.fmt(fmt),
+        }
+    }
+}
+
+/// Parse a count, then run a parser that many times in sequence.
+#[cfg(feature = "alloc")]
+pub fn length_count<'a, Length, Output, Error, LengthError, ChildError>(
+    length: impl Fn(&'a [u8]) -> Step<'a, Length, LengthError>,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<Output>, Error>
+where
+    Length: TryInto<usize>,
+    Error: From<LengthCountError<'a, LengthError, ChildError>>,
+{
+    move |input| {
+        let (rest, count) = match (length)(input) {
+            (rest, Ok(x)) => (rest, x.try_into().unwrap_or(usize::MAX)),
+            (_, Err(e)) => return (input, Err(LengthCountError::Length(e).into())),
+        };
+        // The count comes from the input, so don't trust it for the capacity
+        let mut out = Vec::with_capacity(count.min(rest.len()));
+        let mut at = rest;
+        for step in 0..count {
+            match (child)(at) {
+                (rest, Ok(x)) => {
+                    out.push(x);
+                    at = rest;
+                }
+                (_, Err(child_error)) => {
+                    let error = SeqError {
+                        at,
+                        step,
+                        child_error,
+                    };
+                    return (input, Err(LengthCountError::Child(error).into()));
+                }
+            }
+        }
+        (at, Ok(out))
+    }
+}
+
+#[cfg(feature = "smallvec")]
+use smallvec::{Array, SmallVec};
+
+/// Run a parser N times in sequence, collecting into a [`SmallVec`]. **Requires the `smallvec`
+/// feature**
+#[cfg(feature = "smallvec")]
+pub fn seq_small<'a, A: Array, Error: From<SeqError<'a, ChildError>>, ChildError>(
+    count: usize,
+    child: impl Fn(&'a [u8]) -> Step<'a, A::Item, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, SmallVec<A>, Error> {
+    move |mut input| {
+        let before = input;
+        let mut out = SmallVec::with_capacity(count.min(input.len()));
+        for step in 0..count {
+            let (rest, result) = (child)(input);
+            match result {
+                Ok(x) => out.push(x),
+                Err(child_error) => {
+                    return (
+                        before,
+                        Err(SeqError {
+                            at: input,
+                            step,
+                            child_error,
+                        }
+                        .into()),
+                    )
+                }
+            }
+            input = rest;
+        }
+        (input, Ok(out))
+    }
+}
+
+/// Run two parsers N times each in sequence, zipping their outputs together.
+#[cfg(feature = "alloc")]
+pub fn zip_seq<'a, OutputA, OutputB, Error, ErrorA, ErrorB>(
+    count: usize,
+    a: impl Fn(&'a [u8]) -> Step<'a, OutputA, ErrorA>,
+    b: impl Fn(&'a [u8]) -> Step<'a, OutputB, ErrorB>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<(OutputA, OutputB)>, Error>
+where
+    Error: From<SeqError<'a, ErrorA>> + From<SeqError<'a, ErrorB>>,
+{
+    move |input| {
+        let (mut rest, column) = match seq::<_, SeqError<ErrorA>, _>(count, &a)(input) {
+            (rest, Ok(x)) => (rest, x),
+            (_, Err(e)) => return (input, Err(e.into())),
+        };
+        let mut out = Vec::with_capacity(column.len());
+        for (step, x) in column.into_iter().enumerate() {
+            match (b)(rest) {
+                (next, Ok(y)) => {
+                    out.push((x, y));
+                    rest = next;
+                }
+                (_, Err(child_error)) => {
+                    return (
+                        input,
+                        Err(SeqError {
+                            at: rest,
+                            step,
+                            child_error,
+                        }
+                        .into()),
+                    )
+                }
+            }
+        }
+        (rest, Ok(out))
+    }
+}
+
+/// Run three parsers N times each in sequence, zipping their outputs together.
+#[cfg(feature = "alloc")]
+#[allow(clippy::type_complexity)]
+pub fn zip_seq3<'a, OutputA, OutputB, OutputC, Error, ErrorA, ErrorB, ErrorC>(
+    count: usize,
+    a: impl Fn(&'a [u8]) -> Step<'a, OutputA, ErrorA>,
+    b: impl Fn(&'a [u8]) -> Step<'a, OutputB, ErrorB>,
+    c: impl Fn(&'a [u8]) -> Step<'a, OutputC, ErrorC>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<(OutputA, OutputB, OutputC)>, Error>
+where
+    Error: From<SeqError<'a, ErrorA>> + From<SeqError<'a, ErrorB>> + From<SeqError<'a, ErrorC>>,
+{
+    let ab = zip_seq::<_, _, Error, _, _>(count, a, b);
+    move |input| {
+        let (mut rest, columns) = match (ab)(input) {
+            (rest, Ok(x)) => (rest, x),
+            (_, Err(e)) => return (input, Err(e)),
+        };
+        let mut out = Vec::with_capacity(columns.len());
+        for (step, (x, y)) in columns.into_iter().enumerate() {
+            match (c)(rest) {
+                (next, Ok(z)) => {
+                    out.push((x, y, z));
+                    rest = next;
+                }
+                (_, Err(child_error)) => {
+                    return (
+                        input,
+                        Err(SeqError {
+                            at: rest,
+                            step,
+                            child_error,
+                        }
+                        .into()),
+                    )
+                }
+            }
+        }
+        (rest, Ok(out))
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct FillError<'a, ChildError> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// Offset from the start of the input where the error happened
+    pub offset: usize,
+    /// How many times the parser succeeded before the error
+    pub count: usize,
+    /// The child parser's error, or `None` if it succeeded without consuming input
+    pub child_error: Option<ChildError>,
+}
+
+impl<'a, ChildError: Debug> Debug for FillError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("FillError")
+            .field("at", &InputDebug(self.at))
+            .field("offset", &self.offset)
+            .field("count", &self.count)
+            .field("child_error", &self.child_error)
+            .finish()
+    }
+}
+
+impl<'a, ChildError: Display> Display for FillError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "after {} items at offset {}: ",
+            self.count, self.offset
+        )?;
+        match &self.child_error {
+            Some(e) => e.fmt(fmt),
+            None => write!(fmt, "parser consumed no input"),
+        }
+    }
+}
+
+/// Run a parser repeatedly until the input is exactly consumed.
+#[cfg(feature = "alloc")]
+pub fn count_exact_fill<'a, Output, Error: From<FillError<'a, ChildError>>, ChildError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<Output>, Error> {
+    move |input| {
+        let mut at = input;
+        let mut out = Vec::new();
+        while !at.is_empty() {
+            let child_error = match (child)(at) {
+                (rest, Ok(x)) if rest.len() < at.len() => {
+                    out.push(x);
+                    at = rest;
+                    continue;
+                }
+                (_, Ok(_)) => None,
+                (_, Err(e)) => Some(e),
+            };
+            let error = FillError {
+                at,
+                offset: offset(input, at),
+                count: out.len(),
+                child_error,
+            };
+            return (input, Err(error.into()));
+        }
+        (at, Ok(out))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Many0Error {}
+
+impl Display for Many0Error {
+    fn fmt(&self, _fmt: &mut Formatter<'_>) -> fmt::Result {
+        match *self {}
+    }
+}
+
+/// Run a parser repeatedly until it fails or stops consuming input.
+#[cfg(feature = "alloc")]
+pub fn many0<'a, Output, ChildError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<Output>, Many0Error> {
+    move |mut input| {
+        let mut out = Vec::new();
+        while let (rest, Ok(x)) = (child)(input) {
+            if rest.len() == input.len() {
+                break;
+            }
+            out.push(x);
+            input = rest;
+        }
+        (input, Ok(out))
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Many1Error<'a, ChildError> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The child parser's error, or `None` if it succeeded without consuming input
+    pub child_error: Option<ChildError>,
+}
+
+impl<'a, ChildError: Debug> Debug for Many1Error<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Many1Error")
+            .field("at", &InputDebug(self.at))
+            .field("child_error", &self.child_error)
+            .finish()
+    }
+}
+
+impl<'a, ChildError: Display> Display for Many1Error<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "expected at least one item: ")?;
+        match &self.child_error {
+            Some(e) => e.fmt(fmt),
+            None => write!(fmt, "parser consumed no input"),
+        }
+    }
+}
+
+/// Run a parser repeatedly until it fails or stops consuming input, failing if it didn't succeed
+/// at least once.
+#[cfg(feature = "alloc")]
+pub fn many1<'a, Output, Error: From<Many1Error<'a, ChildError>>, ChildError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<Output>, Error> {
+    move |input| {
+        let child_error = match (child)(input) {
+            (mut at, Ok(x)) if at.len() < input.len() => {
+                let mut out = Vec::from([x]);
+                while let (rest, Ok(x)) = (child)(at) {
+                    if rest.len() == at.len() {
+                        break;
+                    }
+                    out.push(x);
+                    at = rest;
+                }
+                return (at, Ok(out));
+            }
+            (_, Ok(_)) => None,
+            (_, Err(e)) => Some(e),
+        };
+        let error = Many1Error {
+            at: input,
+            child_error,
+        };
+        (input, Err(error.into()))
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ManyMNError<'a, ChildError> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// How many times the parser succeeded before the error
+    pub count: usize,
+    /// The child parser's error, or `None` if it succeeded without consuming input
+    pub child_error: Option<ChildError>,
+}
+
+impl<'a, ChildError: Debug> Debug for ManyMNError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ManyMNError")
+            .field("at", &InputDebug(self.at))
+            .field("count", &self.count)
+            .field("child_error", &self.child_error)
+            .finish()
+    }
+}
+
+impl<'a, ChildError: Display> Display for ManyMNError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "too few items, found {}: ", self.count)?;
+        match &self.child_error {
+            Some(e) => e.fmt(fmt),
+            None => write!(fmt, "parser consumed no input"),
+        }
+    }
+}
+
+/// Run a parser up to `max` times, failing if it didn't succeed at least `min` times.
+#[cfg(feature = "alloc")]
+pub fn many_m_n<'a, Output, Error: From<ManyMNError<'a, ChildError>>, ChildError>(
+    min: usize,
+    max: usize,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<Output>, Error> {
+    move |input| {
+        let mut at = input;
+        let mut out = Vec::new();
+        while out.len() < max {
+            let child_error = match (child)(at) {
+                (rest, Ok(x)) if rest.len() < at.len() => {
+                    out.push(x);
+                    at = rest;
+                    continue;
+                }
+                (_, Ok(_)) => None,
+                (_, Err(e)) => Some(e),
+            };
+            if out.len() < min {
+                let error = ManyMNError {
+                    at,
+                    count: out.len(),
+                    child_error,
+                };
+                return (input, Err(error.into()));
+            }
+            break;
+        }
+        (at, Ok(out))
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ManyTillError<'a, ChildError> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// What iteration the error happened
+    pub step: usize,
+    /// The child parser's error, or `None` if it succeeded without consuming input
+    pub child_error: Option<ChildError>,
+}
+
+impl<'a, ChildError: Debug> Debug for ManyTillError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("ManyTillError")
+            .field("at", &InputDebug(self.at))
+            .field("step", &self.step)
+            .field("child_error", &self.child_error)
+            .finish()
+    }
+}
+
+impl<'a, ChildError: Display> Display for ManyTillError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "step {}: ", self.step)?;
+        match &self.child_error {
+            Some(e) => e.fmt(fmt),
+            None => write!(fmt, "parser consumed no input"),
+        }
+    }
+}
+
+/// Run a parser repeatedly until a terminator parser succeeds, outputting both.
+#[cfg(feature = "alloc")]
+pub fn many_till<'a, Output, TermOutput, Error, ChildError, TermError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+    terminator: impl Fn(&'a [u8]) -> Step<'a, TermOutput, TermError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, (Vec<Output>, TermOutput), Error>
+where
+    Error: From<ManyTillError<'a, ChildError>>,
+{
+    move |input| {
+        let mut at = input;
+        let mut out = Vec::new();
+        loop {
+            if let (rest, Ok(x)) = (terminator)(at) {
+                return (rest, Ok((out, x)));
+            }
+            let child_error = match (child)(at) {
+                (rest, Ok(x)) if rest.len() < at.len() => {
+                    out.push(x);
+                    at = rest;
+                    continue;
+                }
+                (_, Ok(_)) => None,
+                (_, Err(e)) => Some(e),
+            };
+            let error = ManyTillError {
+                at,
+                step: out.len(),
+                child_error,
+            };
+            return (input, Err(error.into()));
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct SeparatedList1Error<'a, ChildError> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The child parser's error
+    pub child_error: ChildError,
+}
+
+impl<'a, ChildError: Debug> Debug for SeparatedList1Error<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("SeparatedList1Error")
+            .field("at", &InputDebug(self.at))
+            .field("child_error", &self.child_error)
+            .finish()
+    }
+}
+
+impl<'a, ChildError: Display> Display for SeparatedList1Error<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "expected at least one item: {}", self.child_error)
+    }
+}
+
+/// Run a parser one or more times, separated by another parser. A separator that isn't followed
+/// by the parser is not consumed.
+#[cfg(feature = "alloc")]
+pub fn separated_list1<'a, Output, Error, SepOutput, SepError, ChildError>(
+    separator: impl Fn(&'a [u8]) -> Step<'a, SepOutput, SepError>,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<Output>, Error>
+where
+    Error: From<SeparatedList1Error<'a, ChildError>>,
+{
+    move |input| {
+        let mut out = Vec::new();
+        let mut at = match (child)(input) {
+            (rest, Ok(x)) => {
+                out.push(x);
+                rest
+            }
+            (_, Err(child_error)) => {
+                let error = SeparatedList1Error {
+                    at: input,
+                    child_error,
+                };
+                return (input, Err(error.into()));
+            }
+        };
+        loop {
+            let rest = match (separator)(at) {
+                (rest, Ok(_)) => rest,
+                (_, Err(_)) => break,
+            };
+            match (child)(rest) {
+                (rest, Ok(x)) if rest.len() < at.len() => {
+                    out.push(x);
+                    at = rest;
+                }
+                _ => break,
+            }
+        }
+        (at, Ok(out))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SeparatedList0Error {}
+
+impl Display for SeparatedList0Error {
+    fn fmt(&self, _fmt: &mut Formatter<'_>) -> fmt::Result {
+        match *self {}
+    }
+}
+
+/// Run a parser zero or more times, separated by another parser. A separator that isn't followed
+/// by the parser is not consumed.
+#[cfg(feature = "alloc")]
+pub fn separated_list0<'a, Output, SepOutput, SepError, ChildError>(
+    separator: impl Fn(&'a [u8]) -> Step<'a, SepOutput, SepError>,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<Output>, SeparatedList0Error> {
+    let list = separated_list1::<_, SeparatedList1Error<_>, _, _, _>(separator, child);
+    move |input| match (list)(input) {
+        (rest, Ok(x)) => (rest, Ok(x)),
+        (_, Err(_)) => (input, Ok(Vec::new())),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SeparatedListTrailingError {}
+
+impl Display for SeparatedListTrailingError {
+    fn fmt(&self, _fmt: &mut Formatter<'_>) -> fmt::Result {
+        match *self {}
+    }
+}
+
+/// Run a parser zero or more times, separated by another parser, allowing a trailing separator.
+/// Stops if the parser and separator together don't consume any input.
+#[cfg(feature = "alloc")]
+pub fn separated_list_trailing<'a, Output, SepOutput, SepError, ChildError>(
+    separator: impl Fn(&'a [u8]) -> Step<'a, SepOutput, SepError>,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Vec<Output>, SeparatedListTrailingError> {
+    move |mut input| {
+        let mut out = Vec::new();
+        loop {
+            let before = input;
+            match (child)(input) {
+                (rest, Ok(x)) => {
+                    out.push(x);
+                    input = rest;
+                }
+                (_, Err(_)) => break,
+            }
+            match (separator)(input) {
+                (rest, Ok(_)) => input = rest,
+                (_, Err(_)) => break,
+            }
+            if input.len() == before.len() {
+                break;
+            }
+        }
+        (input, Ok(out))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OptError {}
+
+impl Display for OptError {
+    fn fmt(&self, _fmt: &mut Formatter<'_>) -> fmt::Result {
+        match *self {}
+    }
+}
+
+pub fn opt<'a, Output, Error>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, Error>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Option<Output>, OptError> {
+    move |input| {
+        let (rest, result) = (child)(input);
+        match result {
+            Ok(x) => (rest, Ok(Some(x))),
+            Err(_) => (input, Ok(None)),
+        }
+    }
+}
+
+/// Allow a parser to fail, outputting [`Default::default`] instead.
+pub fn opt_or_default<'a, Output: Default, Error>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, Error>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, OptError> {
+    move |input| {
+        let (rest, result) = (child)(input);
+        match result {
+            Ok(x) => (rest, Ok(x)),
+            Err(_) => (input, Ok(Output::default())),
+        }
+    }
+}
+
+/// Allow a parser to fail, outputting a clone of `value` instead.
+pub fn unwrap_or<'a, Output: Clone, Error>(
+    value: Output,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, Error>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, OptError> {
+    move |input| {
+        let (rest, result) = (child)(input);
+        match result {
+            Ok(x) => (rest, Ok(x)),
+            Err(_) => (input, Ok(value.clone())),
+        }
+    }
+}
+
+/// Run a parser only if a flag is set, outputting `None` otherwise.
+pub fn cond<'a, Output, Error: From<ChildError>, ChildError>(
+    flag: bool,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Option<Output>, Error> {
+    move |input| match flag {
+        true => {
+            let (rest, x) = step!(input, (child)(input));
+            (rest, Ok(Some(x)))
+        }
+        false => (input, Ok(None)),
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct FinishError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for FinishError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("FinishError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for FinishError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{} bytes left over", self.remaining())
+    }
+}
+
+pub fn finish<'a, Output, Error: From<ChildError> + From<FinishError<'a>>, ChildError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| {
+        let (rest, result) = (child)(input);
+        match result {
+            Ok(x) => match rest.len() {
+                0 => (rest, Ok(x)),
+                _ => (input, Err(FinishError(rest).into())),
+            },
+            Err(e) => (input, Err(e.into())),
+        }
+    }
+}
+
+impl<'a> FinishError<'a> {
+    /// How many bytes were left over
+    pub fn remaining(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct TrailingError<'a> {
+    /// The bytes that were left over
+    pub at: &'a [u8],
+    /// How many bytes were left over
+    pub remaining: usize,
+    /// Offset of the left over bytes from the root buffer
+    pub offset: usize,
+}
+
+impl<'a> Debug for TrailingError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("TrailingError")
+            .field("at", &InputDebug(self.at))
+            .field("remaining", &self.remaining)
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+impl<'a> Display for TrailingError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "{} bytes left over at offset {}",
+            self.remaining, self.offset
+        )
+    }
+}
+
+fn finish_trailing<'a, Output, Error: From<ChildError> + From<TrailingError<'a>>, ChildError>(
+    root: &'a [u8],
+    padded: bool,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| {
+        let (rest, result) = (child)(input);
+        match result {
+            Ok(x) if rest.is_empty() || padded && rest.iter().all(|&b| b == 0) => {
+                (&rest[rest.len()..], Ok(x))
+            }
+            Ok(_) => (
+                input,
+                Err(TrailingError {
+                    at: rest,
+                    remaining: rest.len(),
+                    offset: offset(root, rest),
+                }
+                .into()),
+            ),
+            Err(e) => (input, Err(e.into())),
+        }
+    }
+}
+
+/// Ensure there is no bytes left, reporting left over bytes relative to `root`.
+///
+/// # Panics
+///
+/// Panics if bytes are left over and they aren't inside `root`.
+pub fn finish_strict<'a, Output, Error: From<ChildError> + From<TrailingError<'a>>, ChildError>(
+    root: &'a [u8],
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    finish_trailing(root, false, child)
+}
+
+/// Ensure there is no bytes left other than zero padding, reporting left over bytes relative to
+/// `root`.
+///
+/// # Panics
+///
+/// Panics if bytes are left over and they aren't inside `root`.
+pub fn finish_padded<'a, Output, Error: From<ChildError> + From<TrailingError<'a>>, ChildError>(
+    root: &'a [u8],
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    finish_trailing(root, true, child)
+}
+
+#[derive(Clone, PartialEq)]
+pub struct TagError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for TagError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("TagError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for TagError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "tag not matched")
+    }
+}
+
+impl<'a> From<TakeError<'a>> for TagError<'a> {
+    fn from(x: TakeError<'a>) -> Self {
+        Self(x.0)
+    }
+}
+
+pub fn tag<'a, 'b, Error: From<TagError<'a>>>(
+    key: &'b [u8],
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], Error> + 'b {
+    move |input| match take::<TagError>(key.len())(input) {
+        (rest, Ok(result)) if result == key => (rest, Ok(result)),
+        (_, Err(x)) => (input, Err(x.into())),
+        _ => (input, Err(TagError(input).into())),
+    }
+}
+
+/// Match a sequence of bytes, ignoring ASCII case. Outputs the bytes as they are in the input.
+pub fn tag_no_case<'a, 'b, Error: From<TagError<'a>>>(
+    key: &'b [u8],
+) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], Error> + 'b {
+    move |input| match take::<TagError>(key.len())(input) {
+        (rest, Ok(result)) if result.eq_ignore_ascii_case(key) => (rest, Ok(result)),
+        (_, Err(x)) => (input, Err(x.into())),
+        _ => (input, Err(TagError(input).into())),
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct SkipUntilError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for SkipUntilError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("SkipUntilError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for SkipUntilError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "no match found")
+    }
+}
+
+/// Skip bytes until a parser succeeds, outputting how many bytes were skipped. The parser's
+/// input is not consumed.
+pub fn skip_until<'a, Output, Error: From<SkipUntilError<'a>>, ChildError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, usize, Error> {
+    move |input| {
+        for skipped in 0..=input.len() {
+            let at = &input[skipped..];
+            if (child)(at).1.is_ok() {
+                return (at, Ok(skipped));
+            }
+        }
+        (input, Err(SkipUntilError(input).into()))
+    }
+}
+
+/// Skip bytes until a sequence of bytes is found, outputting how many bytes were skipped. The
+/// sequence of bytes is not consumed.
+pub fn skip_until_tag<'a, 'b, Error: From<SkipUntilError<'a>>>(
+    key: &'b [u8],
+) -> impl Fn(&'a [u8]) -> Step<'a, usize, Error> + 'b {
+    move |input| match find(input, key) {
+        Some(skipped) => (&input[skipped..], Ok(skipped)),
+        None => (input, Err(SkipUntilError(input).into())),
+    }
+}
+
+/// The output of one of two parsers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Either<Left, Right> {
+    Left(Left),
+    Right(Right),
+}
+
+#[derive(Clone, PartialEq)]
+pub struct OrError<'a, Error1, Error2> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The first parser's error
+    pub one: Error1,
+    /// The second parser's error
+    pub two: Error2,
+}
+
+impl<'a, Error1: Debug, Error2: Debug> Debug for OrError<'a, Error1, Error2> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("OrError")
+            .field("at", &InputDebug(self.at))
+            .field("one", &self.one)
+            .field("two", &self.two)
+            .finish()
+    }
+}
+
+impl<'a, Error1: Display, Error2: Display> Display for OrError<'a, Error1, Error2> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "neither parser matched: {}; {}", self.one, self.two)
+    }
+}
+
+/// Try two parsers in order, outputting whichever succeeds first. Fails with the first parser's
+/// error without trying the second if it isn't [`Recoverable`].
+pub fn or<'a, Output1, Output2, Error, Error1, Error2>(
+    one: impl Fn(&'a [u8]) -> Step<'a, Output1, Error1>,
+    two: impl Fn(&'a [u8]) -> Step<'a, Output2, Error2>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Either<Output1, Output2>, Error>
+where
+    Error: From<OrError<'a, Error1, Error2>> + From<Error1> + From<Error2>,
+    Error1: Recoverable,
+    Error2: Recoverable,
+{
+    move |input| {
+        let one = match (one)(input) {
+            (rest, Ok(x)) => return (rest, Ok(Either::Left(x))),
+            (_, Err(e)) if !e.is_recoverable() => return (input, Err(e.into())),
+            (_, Err(e)) => e,
+        };
+        match (two)(input) {
+            (rest, Ok(x)) => (rest, Ok(Either::Right(x))),
+            (_, Err(two)) if !two.is_recoverable() => (input, Err(two.into())),
+            (_, Err(two)) => (
+                input,
+                Err(OrError {
+                    at: input,
+                    one,
+                    two,
+                }
+                .into()),
+            ),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct CutError<'a, ChildError> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The child parser's error
+    pub child_error: ChildError,
+}
+
+impl<'a, ChildError: Debug> Debug for CutError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("CutError")
+            .field("at", &InputDebug(self.at))
+            .field("child_error", &self.child_error)
+            .finish()
+    }
+}
+
+impl<'a, ChildError: Display> Display for CutError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.child_error, fmt)
+    }
+}
+
+/// Make a parser's errors unrecoverable, so [`or`] and [`alt`] fail with it instead of trying
+/// the other branches. Use it after the part of a branch that tells it apart from the others.
+pub fn cut<'a, Output, Error, ChildError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error>
+where
+    Error: From<CutError<'a, ChildError>>,
+{
+    move |input| match (child)(input) {
+        (rest, Ok(x)) => (rest, Ok(x)),
+        (_, Err(child_error)) => (
+            input,
+            Err(CutError {
+                at: input,
+                child_error,
+            }
+            .into()),
+        ),
+    }
+}
+
+pub fn and<'a, Output1, Output2, Error: From<Error1> + From<Error2>, Error1, Error2>(
+    one: impl Fn(&'a [u8]) -> Step<'a, Output1, Error1>,
+    two: impl Fn(&'a [u8]) -> Step<'a, Output2, Error2>,
+) -> impl Fn(&'a [u8]) -> Step<'a, (Output1, Output2), Error> {
+    move |input| {
+        let before = input;
+        match (one)(input) {
+            (rest, Ok(x)) => match (two)(rest) {
+                (rest, Ok(y)) => (rest, Ok((x, y))),
+                (_, Err(e)) => (before, Err(e.into())),
+            },
+            (_, Err(e)) => (before, Err(e.into())),
+        }
+    }
+}
+
+/// Run two parsers in sequence, outputting only the second's output.
+pub fn preceded<'a, Output, Error, PrefixOutput, PrefixError, ChildError>(
+    prefix: impl Fn(&'a [u8]) -> Step<'a, PrefixOutput, PrefixError>,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error>
+where
+    Error: From<PrefixError> + From<ChildError>,
+{
+    move |input| {
+        let (rest, _) = step!(input, (prefix)(input));
+        let (rest, x) = step!(input, (child)(rest));
+        (rest, Ok(x))
+    }
+}
+
+/// Run two parsers in sequence, outputting only the first's output.
+pub fn terminated<'a, Output, Error, ChildError, SuffixOutput, SuffixError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+    suffix: impl Fn(&'a [u8]) -> Step<'a, SuffixOutput, SuffixError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error>
+where
+    Error: From<ChildError> + From<SuffixError>,
+{
+    move |input| {
+        let (rest, x) = step!(input, (child)(input));
+        let (rest, _) = step!(input, (suffix)(rest));
+        (rest, Ok(x))
+    }
+}
+
+/// Run three parsers in sequence, outputting only the middle one's output.
+pub fn delimited<'a, Output, Error, OpenOutput, OpenError, ChildError, CloseOutput, CloseError>(
+    open: impl Fn(&'a [u8]) -> Step<'a, OpenOutput, OpenError>,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+    close: impl Fn(&'a [u8]) -> Step<'a, CloseOutput, CloseError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error>
+where
+    Error: From<OpenError> + From<ChildError> + From<CloseError>,
+{
+    move |input| {
+        let (rest, _) = step!(input, (open)(input));
+        let (rest, x) = step!(input, (child)(rest));
+        let (rest, _) = step!(input, (close)(rest));
+        (rest, Ok(x))
+    }
+}
+
+/// Run three parsers in sequence, outputting the first's and last's outputs.
+pub fn separated_pair<'a, Output1, Output2, Error, Error1, SepOutput, SepError, Error2>(
+    one: impl Fn(&'a [u8]) -> Step<'a, Output1, Error1>,
+    separator: impl Fn(&'a [u8]) -> Step<'a, SepOutput, SepError>,
+    two: impl Fn(&'a [u8]) -> Step<'a, Output2, Error2>,
+) -> impl Fn(&'a [u8]) -> Step<'a, (Output1, Output2), Error>
+where
+    Error: From<Error1> + From<SepError> + From<Error2>,
+{
+    move |input| {
+        let (rest, x) = step!(input, (one)(input));
+        let (rest, _) = step!(input, (separator)(rest));
+        let (rest, y) = step!(input, (two)(rest));
+        (rest, Ok((x, y)))
+    }
+}
+
+/// Convert a parser's output and error with [`Into`].
+pub fn into<'a, Output, Error, ChildOutput: Into<Output>, ChildError: Into<Error>>(
+    child: impl Fn(&'a [u8]) -> Step<'a, ChildOutput, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| {
+        let (rest, result) = (child)(input);
+        (rest, result.map(Into::into).map_err(Into::into))
+    }
+}
+
+/// Run a parser, outputting a clone of `value` instead of its output.
+pub fn value<'a, Output: Clone, Error, ChildOutput>(
+    value: Output,
+    child: impl Fn(&'a [u8]) -> Step<'a, ChildOutput, Error>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| {
+        let (rest, result) = (child)(input);
+        (rest, result.map(|_| value.clone()))
+    }
+}
+
+/// Run a parser, discarding its output.
+pub fn skip<'a, Output, Error>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, Error>,
+) -> impl Fn(&'a [u8]) -> Step<'a, (), Error> {
+    move |input| {
+        let (rest, result) = (child)(input);
+        (rest, result.map(|_| ()))
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub enum MapResError<'a, ChildError, MapError> {
+    /// The child parser's error
+    Child(ChildError),
+    /// The conversion's error
+    Map {
+        /// Where the error happened
+        at: &'a [u8],
+        map_error: MapError,
+    },
+}
+
+impl<'a, ChildError: Debug, MapError: Debug> Debug for MapResError<'a, ChildError, MapError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Child(e) => fmt.debug_tuple("Child").field(e).finish(),
+            Self::Map { at, map_error } => fmt
+                .debug_struct("Map")
+                .field("at", &InputDebug(at))
+                .field("map_error", map_error)
+                .finish(),
+        }
+    }
+}
+
+impl<'a, ChildError: Display, MapError: Display> Display for MapResError<'a, ChildError, MapError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Child(e) => e.fmt(fmt),
+            Self::Map { map_error, .. } => write!(fmt, "conversion failed: {}", map_error),
+        }
+    }
+}
+
+/// Convert a parser's output with a function that can fail, like [`TryFrom::try_from`].
+pub fn map_res<'a, Output, Error, ChildOutput, ChildError, MapError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, ChildOutput, ChildError>,
+    map: impl Fn(ChildOutput) -> Result<Output, MapError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error>
+where
+    Error: From<MapResError<'a, ChildError, MapError>>,
+{
+    move |input| match (child)(input) {
+        (rest, Ok(x)) => match (map)(x) {
+            Ok(x) => (rest, Ok(x)),
+            Err(map_error) => (
+                input,
+                Err(MapResError::Map {
+                    at: input,
+                    map_error,
+                }
+                .into()),
+            ),
+        },
+        (_, Err(e)) => (input, Err(MapResError::Child(e).into())),
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct VerifyError<'a, Output> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The output that was rejected
+    pub value: Output,
+}
+
+impl<'a, Output: Debug> Debug for VerifyError<'a, Output> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("VerifyError")
+            .field("at", &InputDebug(self.at))
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<'a, Output: Debug> Display for VerifyError<'a, Output> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{:?} was rejected", self.value)
+    }
+}
+
+/// Label a parser's errors with what it was parsing.
+pub fn context<'a, Output, Error: ContextError<'a>>(
+    label: &'static str,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, Error>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| match (child)(input) {
+        (rest, Ok(x)) => (rest, Ok(x)),
+        (_, Err(e)) => (input, Err(e.add_context(input, label))),
+    }
+}
 
-extern crate alloc;
+/// Fail if a parser's output doesn't satisfy a predicate.
+pub fn verify<'a, Output, Error, ChildError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+    predicate: impl Fn(&Output) -> bool,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error>
+where
+    Error: From<ChildError> + From<VerifyError<'a, Output>>,
+{
+    move |input| {
+        let (rest, x) = step!(input, (child)(input));
+        match (predicate)(&x) {
+            true => (rest, Ok(x)),
+            false => (
+                input,
+                Err(VerifyError {
+                    at: input,
+                    value: x,
+                }
+                .into()),
+            ),
+        }
+    }
+}
 
-use alloc::vec::Vec;
-use core::fmt::{self, Debug, Formatter};
+/// Run a parser, then run the parser made from its output on the rest of the input.
+pub fn and_then<'a, Output, Error, ChildOutput, ChildError, Next, NextError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, ChildOutput, ChildError>,
+    next: impl Fn(ChildOutput) -> Next,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error>
+where
+    Next: Fn(&'a [u8]) -> Step<'a, Output, NextError>,
+    Error: From<ChildError> + From<NextError>,
+{
+    move |input| {
+        let (rest, x) = step!(input, (child)(input));
+        let (rest, y) = step!(input, (next)(x)(rest));
+        (rest, Ok(y))
+    }
+}
 
-pub type Step<'a, Output, Error> = (&'a [u8], Result<Output, Error>);
+/// Byte offset of `at` within `root`, or `None` if `at` doesn't start inside `root`.
+pub(crate) fn offset_in(root: &[u8], at: &[u8]) -> Option<usize> {
+    let at = at.as_ptr() as usize;
+    let start = root.as_ptr() as usize;
+    (start..=start + root.len())
+        .contains(&at)
+        .then(|| at - start)
+}
 
-pub struct ByteError;
+/// Byte offset of `at` within `root`, for combinators that take `root` from the caller.
+///
+/// # Panics
+///
+/// Panics if `at` doesn't start inside `root`, like when a parser is given the wrong root.
+fn offset(root: &[u8], at: &[u8]) -> usize {
+    offset_in(root, at).expect("input isn't inside root")
+}
 
-pub fn byte<'a, Error: From<ByteError>>(input: &'a [u8]) -> Step<'a, u8, Error> {
-    match input.split_first() {
-        Some((&byte, rest)) => (rest, Ok(byte)),
-        None => (input, Err(ByteError.into())),
+/// Output the byte range a parser consumed, relative to `root`, along with its output.
+///
+/// # Panics
+///
+/// Panics if the input isn't inside `root`.
+pub fn spanned<'a, Output, Error>(
+    root: &'a [u8],
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, Error>,
+) -> impl Fn(&'a [u8]) -> Step<'a, (Range<usize>, Output), Error> {
+    move |input| {
+        let (rest, result) = (child)(input);
+        (
+            rest,
+            result.map(|x| (offset(root, input)..offset(root, rest), x)),
+        )
     }
 }
 
-pub struct TakeError<'a>(
-    /// Where the error happened
-    pub &'a [u8],
-);
-
-pub fn take<'a, Error: From<TakeError<'a>>>(
-    count: usize,
+/// Output the bytes a parser consumed instead of its output.
+pub fn recognize<'a, Output, Error>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, Error>,
 ) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], Error> {
     move |input| {
-        let (out, input) = input.split_at(count);
-        match out.len() {
-            0 if count != 0 => (input, Err(TakeError(input).into())),
-            _ => (input, Ok(out)),
-        }
+        let (rest, result) = (child)(input);
+        (rest, result.map(|_| &input[..input.len() - rest.len()]))
     }
 }
 
-pub struct SeqError<'a, ChildError> {
+/// Output the bytes a parser consumed along with its output.
+pub fn consumed<'a, Output, Error>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, Error>,
+) -> impl Fn(&'a [u8]) -> Step<'a, (&'a [u8], Output), Error> {
+    move |input| {
+        let (rest, result) = (child)(input);
+        (
+            rest,
+            result.map(|x| (&input[..input.len() - rest.len()], x)),
+        )
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct ParseAtError<'a> {
     /// Where the error happened
     pub at: &'a [u8],
-    /// What iteration the error happened
-    pub step: usize,
-    /// The child parser's error
-    pub child_error: ChildError,
+    /// The offset that was out of bounds
+    pub offset: usize,
 }
 
-impl<'a, ChildError: Debug> Debug for SeqError<'a, ChildError> {
+impl<'a> Debug for ParseAtError<'a> {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        fmt.debug_struct("SeqError")
-            .field("step", &self.step)
-            .field("child_error", &self.child_error)
-            // .field("input", &InputDebug(self.input))
+        fmt.debug_struct("ParseAtError")
+            .field("at", &InputDebug(self.at))
+            .field("offset", &self.offset)
             .finish()
     }
 }
 
-pub fn seq<'a, Output, Error: From<SeqError<'a, ChildError>>, ChildError>(
-    count: usize,
-    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
-) -> impl Fn(&'a [u8]) -> Step<'a, Vec<Output>, Error> {
-    move |mut input| {
-        let before = input;
-        let mut out = Vec::with_capacity(count);
-        for step in 0..count {
-            let (rest, result) = (child)(input);
-            match result {
-                Ok(x) => out.push(x),
-                Err(child_error) => {
-                    return (
-                        before,
-                        Err(SeqError {
-                            at: input,
-                            step,
-                            child_error,
-                        }
-                        .into()),
-                    )
-                }
-            }
-            input = rest;
-        }
-        (input, Ok(out))
+impl<'a> Display for ParseAtError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "offset {} is out of bounds", self.offset)
     }
 }
 
-pub enum OptError {}
+/// Run a parser at an offset of `root` without consuming any input.
+pub fn parse_at<'a, Output, Error: From<ChildError> + From<ParseAtError<'a>>, ChildError>(
+    root: &'a [u8],
+    offset: usize,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| match root.get(offset..) {
+        Some(at) => (input, (child)(at).1.map_err(Into::into)),
+        None => (input, Err(ParseAtError { at: input, offset }.into())),
+    }
+}
 
-pub fn opt<'a, Output, Error, Parser>(
+/// Run a parser without consuming any input.
+pub fn peek<'a, Output, Error>(
     child: impl Fn(&'a [u8]) -> Step<'a, Output, Error>,
-) -> impl Fn(&'a [u8]) -> Step<'a, Option<Output>, OptError> {
-    move |input| {
-        let (rest, result) = (child)(input);
-        match result {
-            Ok(x) => (rest, Ok(Some(x))),
-            Err(_) => (input, Ok(None)),
-        }
-    }
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| (input, (child)(input).1)
 }
 
-pub struct FinishError<'a>(
+#[derive(Clone, PartialEq)]
+pub struct NotError<'a>(
     /// Where the error happened
     pub &'a [u8],
 );
 
-pub fn finish<'a, Output, Error: From<ChildError> + From<FinishError<'a>>, ChildError>(
+impl<'a> Debug for NotError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("NotError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for NotError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "parser unexpectedly matched")
+    }
+}
+
+/// Succeed only if a parser fails, without consuming any input.
+pub fn not<'a, Output, Error: From<NotError<'a>>, ChildError>(
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, (), Error> {
+    move |input| match (child)(input) {
+        (_, Ok(_)) => (input, Err(NotError(input).into())),
+        (_, Err(_)) => (input, Ok(())),
+    }
+}
+
+/// Run a parser on the next N bytes without consuming any input.
+pub fn lookahead<'a, Output, Error: From<ChildError> + From<TakeError<'a>>, ChildError>(
+    count: usize,
     child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
 ) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
-    move |input| {
-        let (rest, result) = (child)(input);
-        match result {
-            Ok(x) => match input.len() {
-                0 => (rest, Ok(x)),
-                _ => (input, Err(FinishError(input).into())),
-            },
-            Err(e) => (input, Err(e.into())),
-        }
+    move |input| match take::<TakeError>(count)(input) {
+        (_, Ok(window)) => (input, (child)(window).1.map_err(Into::into)),
+        (_, Err(e)) => (input, Err(e.into())),
     }
 }
 
-pub struct TagError<'a>(
+#[derive(Clone, PartialEq)]
+pub struct LimitError<'a> {
     /// Where the error happened
-    pub &'a [u8],
-);
-
-impl<'a> From<TakeError<'a>> for TagError<'a> {
-    fn from(x: TakeError<'a>) -> Self {
-        Self(x.0)
-    }
+    pub at: &'a [u8],
+    /// The most bytes the parser was allowed to consume
+    pub limit: usize,
+    /// How many bytes the parser consumed
+    pub consumed: usize,
 }
 
-pub fn tag<'a, 'b, Error: From<TagError<'a>>>(
-    key: &'b [u8],
-) -> impl Fn(&'a [u8]) -> Step<'a, &'a [u8], Error> + 'b {
-    move |input| match take::<TagError>(key.len())(input) {
-        (rest, Ok(result)) if result == key => (rest, Ok(result)),
-        (_, Err(x)) => (input, Err(x.into())),
-        _ => (input, Err(TagError(input).into())),
+impl<'a> Debug for LimitError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("LimitError")
+            .field("at", &InputDebug(self.at))
+            .field("limit", &self.limit)
+            .field("consumed", &self.consumed)
+            .finish()
     }
 }
 
-pub fn or<'a, Output1, Output2, Error: From<Error2>, Error1, Error2>(
-    one: impl Fn(&'a [u8]) -> Step<'a, Output1, Error1>,
-    two: impl Fn(&'a [u8]) -> Step<'a, Output2, Error2>,
-) -> impl Fn(&'a [u8]) -> Step<'a, (Option<Output1>, Option<Output2>), Error> {
-    move |input| {
-        let (rest, result1) = (one)(input);
-        let input = if result1.is_ok() { rest } else { input };
-        let (rest, result2) = (two)(input);
-        (rest, Ok((result1.ok(), result2.ok())))
+impl<'a> Display for LimitError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            fmt,
+            "consumed {} bytes, more than the limit of {}",
+            self.consumed, self.limit
+        )
     }
 }
 
-pub fn and<'a, Output1, Output2, Error: From<Error1> + From<Error2>, Error1, Error2>(
-    one: impl Fn(&'a [u8]) -> Step<'a, Output1, Error1>,
-    two: impl Fn(&'a [u8]) -> Step<'a, Output2, Error2>,
-) -> impl Fn(&'a [u8]) -> Step<'a, (Output1, Output2), Error> {
-    move |input| {
-        let before = input;
-        match (one)(input) {
-            (rest, Ok(x)) => match (two)(rest) {
-                (rest, Ok(y)) => (rest, Ok((x, y))),
-                (_, Err(e)) => (before, Err(e.into())),
-            },
-            (_, Err(e)) => (before, Err(e.into())),
-        }
+/// Fail if a parser consumes more than N bytes.
+pub fn limit<'a, Output, Error: From<ChildError> + From<LimitError<'a>>, ChildError>(
+    limit: usize,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| match (child)(input) {
+        (rest, Ok(x)) => match input.len() - rest.len() {
+            consumed if consumed > limit => (
+                input,
+                Err(LimitError {
+                    at: input,
+                    limit,
+                    consumed,
+                }
+                .into()),
+            ),
+            _ => (rest, Ok(x)),
+        },
+        (_, Err(e)) => (input, Err(e.into())),
     }
 }
 
@@ -198,17 +2725,36 @@ pub fn and<'a, Output1, Output2, Error: From<Error1> + From<Error2>, Error1, Err
 use bytemuck::{Pod, PodCastError};
 
 #[cfg(feature = "bytemuck")]
+#[derive(Clone, PartialEq)]
 pub struct PodError<'a> {
     /// Where the error happened
     pub at: &'a [u8],
     pub pod_error: PodCastError,
 }
 
+#[cfg(feature = "bytemuck")]
+impl<'a> Debug for PodError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("PodError")
+            .field("at", &InputDebug(self.at))
+            .field("pod_error", &self.pod_error)
+            .finish()
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl<'a> Display for PodError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "can't cast bytes: {}", self.pod_error)
+    }
+}
+
 #[cfg(feature = "bytemuck")]
 pub fn pod<'a, Output: Pod, Error: From<PodError<'a>>>(
     input: &'a [u8],
 ) -> Step<'a, &'a Output, Error> {
-    let (rest, bytes) = input.split_at(core::mem::size_of::<Output>());
+    let size = core::mem::size_of::<Output>();
+    let (bytes, rest) = input.split_at(size.min(input.len()));
     match bytemuck::try_from_bytes(bytes) {
         Ok(x) => (rest, Ok(x)),
         Err(pod_error) => (
@@ -222,19 +2768,75 @@ pub fn pod<'a, Output: Pod, Error: From<PodError<'a>>>(
     }
 }
 
+/// Copy bytes into a type, which unlike [`pod`] works on input that isn't aligned for it.
+#[cfg(feature = "bytemuck")]
+pub fn pod_copied<'a, Output: Pod, Error: From<PodError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, Output, Error> {
+    let size = core::mem::size_of::<Output>();
+    let (bytes, rest) = input.split_at(size.min(input.len()));
+    match bytemuck::try_pod_read_unaligned(bytes) {
+        Ok(x) => (rest, Ok(x)),
+        Err(pod_error) => (
+            input,
+            Err(PodError {
+                at: input,
+                pod_error,
+            }
+            .into()),
+        ),
+    }
+}
+
+/// Define the error for a number parser, for a number of `$size` bytes.
+macro_rules! num_error {
+    ($err_name:ident, $size:expr, $name:expr) => {
+        #[derive(Clone, PartialEq)]
+        pub struct $err_name<'a>(
+            /// Where the error happened
+            pub &'a [u8],
+        );
+
+        impl<'a> Debug for $err_name<'a> {
+            fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+                fmt.debug_tuple(stringify!($err_name))
+                    .field(&InputDebug(self.0))
+                    .finish()
+            }
+        }
+
+        impl<'a> Display for $err_name<'a> {
+            fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+                write!(
+                    fmt,
+                    "expected {} bytes for {}, {} left",
+                    $size,
+                    $name,
+                    self.0.len()
+                )
+            }
+        }
+
+        std_error! {
+            $err_name<'a>;
+        }
+    };
+}
+
 macro_rules! num_impl {
     (
         $(#[$m:meta])*
         $num_ty:ty, $endian_fn:ident, $fn_name:ident, $err_name:ident;
         $($rest:tt)*
     ) => {
-        pub struct $err_name<'a>(&'a [u8]);
+        num_error!($err_name, core::mem::size_of::<$num_ty>(), stringify!($num_ty));
 
         $(#[$m])*
         pub fn $fn_name<'a, Error: From<$err_name<'a>>>(
             input: &'a [u8]
-        ) -> Step<'a, $num_ty, $err_name> {
-            let (out, rest) = input.split_at(core::mem::size_of::<$num_ty>());
+        ) -> Step<'a, $num_ty, Error> {
+            let size = core::mem::size_of::<$num_ty>();
+            let (out, rest) = input.split_at(size.min(input.len()));
             let out = match out.try_into() {
                 Ok(x) => x,
                 Err(_) => return (input, Err($err_name(input).into())),
@@ -248,6 +2850,9 @@ macro_rules! num_impl {
 }
 
 num_impl! {
+    /// Parse signed 8-bit integer.
+    i8, from_le_bytes, sbyte, SByteError;
+
     /// Parse unsigned 16-bit little-endian integer.
     u16, from_le_bytes, u16l, U16LError;
     /// Parse signed 16-bit little-endian integer.
@@ -293,4 +2898,283 @@ num_impl! {
     f64, from_le_bytes, f64l, F64LError;
     /// Parse 64-bit big-endian float.
     f64, from_be_bytes, f64b, F64BError;
+
+    /// Parse unsigned 16-bit native-endian integer.
+    u16, from_ne_bytes, u16n, U16NError;
+    /// Parse signed 16-bit native-endian integer.
+    i16, from_ne_bytes, i16n, I16NError;
+    /// Parse unsigned 32-bit native-endian integer.
+    u32, from_ne_bytes, u32n, U32NError;
+    /// Parse signed 32-bit native-endian integer.
+    i32, from_ne_bytes, i32n, I32NError;
+    /// Parse unsigned 64-bit native-endian integer.
+    u64, from_ne_bytes, u64n, U64NError;
+    /// Parse signed 64-bit native-endian integer.
+    i64, from_ne_bytes, i64n, I64NError;
+    /// Parse unsigned 128-bit native-endian integer.
+    u128, from_ne_bytes, u128n, U128NError;
+    /// Parse signed 128-bit native-endian integer.
+    i128, from_ne_bytes, i128n, I128NError;
+    /// Parse 32-bit native-endian float.
+    f32, from_ne_bytes, f32n, F32NError;
+    /// Parse 64-bit native-endian float.
+    f64, from_ne_bytes, f64n, F64NError;
+}
+
+/// The `$size` most significant bytes of an array in the given byte order.
+macro_rules! most_significant {
+    (from_le_bytes, $bytes:ident, $width:expr, $size:expr) => {
+        $bytes[$width - $size..]
+    };
+    (from_be_bytes, $bytes:ident, $width:expr, $size:expr) => {
+        $bytes[..$size]
+    };
+}
+
+/// Parse integers that are narrower than their type, sign extending the signed ones.
+macro_rules! narrow_int_impl {
+    (
+        $(#[$m:meta])*
+        $num_ty:ty, $size:expr, $name:ident, $endian_fn:ident, $fn_name:ident, $err_name:ident;
+        $($rest:tt)*
+    ) => {
+        num_error!($err_name, $size, stringify!($name));
+
+        $(#[$m])*
+        pub fn $fn_name<'a, Error: From<$err_name<'a>>>(
+            input: &'a [u8]
+        ) -> Step<'a, $num_ty, Error> {
+            const WIDTH: usize = core::mem::size_of::<$num_ty>();
+            if input.len() < $size {
+                return (input, Err($err_name(input).into()));
+            }
+            let (out, rest) = input.split_at($size);
+            let mut bytes = [0; WIDTH];
+            // Put the bytes at the most significant end, then shift them down
+            most_significant!($endian_fn, bytes, WIDTH, $size).copy_from_slice(out);
+            let shift = (WIDTH - $size) * 8;
+            (rest, Ok(<$num_ty>::$endian_fn(bytes) >> shift))
+        }
+
+        narrow_int_impl! { $($rest)* }
+    };
+    () => {}
+}
+
+narrow_int_impl! {
+    /// Parse unsigned 24-bit little-endian integer.
+    u32, 3, u24, from_le_bytes, u24l, U24LError;
+    /// Parse signed 24-bit little-endian integer.
+    i32, 3, i24, from_le_bytes, i24l, I24LError;
+    /// Parse unsigned 24-bit big-endian integer.
+    u32, 3, u24, from_be_bytes, u24b, U24BError;
+    /// Parse signed 24-bit big-endian integer.
+    i32, 3, i24, from_be_bytes, i24b, I24BError;
+
+    /// Parse unsigned 48-bit little-endian integer.
+    u64, 6, u48, from_le_bytes, u48l, U48LError;
+    /// Parse unsigned 48-bit big-endian integer.
+    u64, 6, u48, from_be_bytes, u48b, U48BError;
+}
+
+/// Widen an IEEE 754 half float to a float, which can represent every half float exactly.
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = (bits & 0x8000) != 0;
+    let exponent = u32::from(bits >> 10) & 0x1f;
+    let mantissa = u32::from(bits & 0x3ff);
+    let magnitude = match exponent {
+        // Zero and subnormals, which are normal as a float
+        0 => mantissa as f32 / (1 << 24) as f32,
+        // Infinity and NaN, keeping the payload
+        0x1f => f32::from_bits(0x7f80_0000 | mantissa << 13),
+        _ => f32::from_bits((exponent + 127 - 15) << 23 | mantissa << 13),
+    };
+    match sign {
+        true => -magnitude,
+        false => magnitude,
+    }
+}
+
+num_error!(F16LError, 2, "f16");
+num_error!(F16BError, 2, "f16");
+
+/// Parse 16-bit little-endian float, widened to `f32`.
+pub fn f16l<'a, Error: From<F16LError<'a>>>(input: &'a [u8]) -> Step<'a, f32, Error> {
+    match u16l::<U16LError>(input) {
+        (rest, Ok(x)) => (rest, Ok(f16_to_f32(x))),
+        (_, Err(_)) => (input, Err(F16LError(input).into())),
+    }
+}
+
+/// Parse 16-bit big-endian float, widened to `f32`.
+pub fn f16b<'a, Error: From<F16BError<'a>>>(input: &'a [u8]) -> Step<'a, f32, Error> {
+    match u16b::<U16BError>(input) {
+        (rest, Ok(x)) => (rest, Ok(f16_to_f32(x))),
+        (_, Err(_)) => (input, Err(F16BError(input).into())),
+    }
+}
+
+/// Parse signed 16.16 fixed-point little-endian number, like TrueType's `Fixed`.
+pub fn fixed16_16l<'a, Error: From<I32LError<'a>>>(input: &'a [u8]) -> Step<'a, f64, Error> {
+    let (rest, x) = step!(input, i32l::<Error>(input));
+    (rest, Ok(f64::from(x) / 65536.0))
+}
+
+/// Parse signed 16.16 fixed-point big-endian number, like TrueType's `Fixed`.
+pub fn fixed16_16b<'a, Error: From<I32BError<'a>>>(input: &'a [u8]) -> Step<'a, f64, Error> {
+    let (rest, x) = step!(input, i32b::<Error>(input));
+    (rest, Ok(f64::from(x) / 65536.0))
+}
+
+/// Parse signed 2.14 fixed-point little-endian number, like TrueType's `F2Dot14`.
+pub fn fixed2_14l<'a, Error: From<I16LError<'a>>>(input: &'a [u8]) -> Step<'a, f32, Error> {
+    let (rest, x) = step!(input, i16l::<Error>(input));
+    (rest, Ok(f32::from(x) / 16384.0))
+}
+
+/// Parse signed 2.14 fixed-point big-endian number, like TrueType's `F2Dot14`.
+pub fn fixed2_14b<'a, Error: From<I16BError<'a>>>(input: &'a [u8]) -> Step<'a, f32, Error> {
+    let (rest, x) = step!(input, i16b::<Error>(input));
+    (rest, Ok(f32::from(x) / 16384.0))
+}
+
+std_error! {
+    ByteError<'a>;
+    ByteMapError<'a>;
+    SatisfyError<'a>;
+    BoolError<'a>;
+    EnumError<'a, Discriminant>;
+    FlagsError<'a, Bits>;
+    TakeError<'a>;
+    LengthDataError<'a, LengthError>;
+    TakeWhileError;
+    RestError;
+    Many0Error;
+    SeparatedList0Error;
+    SeparatedListTrailingError;
+    OptError;
+    TakeWhile1Error<'a>;
+    TakeUntilError<'a>;
+    TakeTillError<'a>;
+    CStrError<'a>;
+    UnstuffError<'a>;
+    SeqError<'a, ChildError>;
+    LengthCountError<'a, LengthError, ChildError>;
+    FillError<'a, ChildError>;
+    Many1Error<'a, ChildError>;
+    ManyMNError<'a, ChildError>;
+    ManyTillError<'a, ChildError>;
+    SeparatedList1Error<'a, ChildError>;
+    FinishError<'a>;
+    TrailingError<'a>;
+    TagError<'a>;
+    SkipUntilError<'a>;
+    OrError<'a, Error1, Error2>;
+    CutError<'a, ChildError>;
+    MapResError<'a, ChildError, MapError>;
+    VerifyError<'a, Output>;
+    ParseAtError<'a>;
+    NotError<'a>;
+    LimitError<'a>;
+}
+
+#[cfg(feature = "bytemuck")]
+std_error! {
+    PodError<'a>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spanned_range() {
+        let root = [1, 2, 3, 4];
+        let (rest, x) = spanned(&root, u16l::<U16LError>)(&root[1..]);
+        assert_eq!(rest, &[4]);
+        assert_eq!(x.unwrap(), (1..3, 0x0302));
+    }
+
+    #[test]
+    #[should_panic(expected = "input isn't inside root")]
+    fn spanned_wrong_root() {
+        let buffer = [1, 2, 3, 4, 5, 6];
+        let _ = spanned(&buffer[..2], u16l::<U16LError>)(&buffer[3..]);
+    }
+
+    #[cfg(feature = "bitflags")]
+    bitflags::bitflags! {
+        #[derive(Debug, PartialEq)]
+        struct Mode: u8 {
+            const READ = 1;
+            const WRITE = 2;
+        }
+    }
+
+    #[cfg(feature = "bitflags")]
+    #[test]
+    fn flags_from_unknown_bits() {
+        let (_, x) =
+            flags_from::<Mode, ParseError, _>(UnknownBits::Reject, byte::<ParseError>)(&[0b111]);
+        assert_eq!(x.unwrap_err().kind, ErrorKind::Flags);
+        let (_, x) =
+            flags_from::<Mode, ParseError, _>(UnknownBits::Truncate, byte::<ParseError>)(&[0b111]);
+        assert_eq!(x.unwrap(), Mode::READ | Mode::WRITE);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn separated_list_trailing_no_progress() {
+        // Both the parser and the separator succeed without consuming anything
+        let input = b"yz";
+        let parser = separated_list_trailing(opt(tag::<TagError>(b",")), take_while(|b| b == b'x'));
+        let (rest, x) = parser(input);
+        assert_eq!(rest, input);
+        assert_eq!(x.unwrap(), [&[]]);
+    }
+
+    #[test]
+    fn limit_child_error_keeps_input() {
+        // A child that doesn't rewind on error
+        fn child(input: &[u8]) -> Step<'_, u8, ParseError<'_>> {
+            (&input[1..], Err(ByteError { at: &input[1..] }.into()))
+        }
+        let input = b"ab";
+        let (rest, x) = limit::<_, ParseError, _>(4, child)(input);
+        assert_eq!(rest, input);
+        assert_eq!(x.unwrap_err().kind, ErrorKind::Byte);
+    }
+
+    #[test]
+    fn one_of_errors() {
+        let input = b"x";
+        let (rest, x) = one_of::<ParseError>(b"ab")(input);
+        assert_eq!(rest, input);
+        assert_eq!(x.unwrap_err().kind, ErrorKind::Satisfy);
+        let (_, x) = none_of::<ParseError>(b"x")(input);
+        assert_eq!(x.unwrap_err().kind, ErrorKind::Satisfy);
+        let (_, x) = one_of::<ParseError>(b"ab")(&input[1..]);
+        assert_eq!(x.unwrap_err().kind, ErrorKind::Byte);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn seq_huge_count() {
+        // Preallocating the count would overflow the capacity before the input runs out
+        let input = b"ab";
+        let (rest, x) = seq::<_, SeqError<ByteError>, _>(usize::MAX, byte)(input);
+        assert_eq!(rest, input);
+        assert_eq!(x.unwrap_err().step, 2);
+        let (_, x) =
+            zip_seq::<_, _, ParseError, ParseError, ParseError>(usize::MAX, byte, byte)(input);
+        assert_eq!(x.unwrap_err().kind, ErrorKind::Byte);
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn seq_small_huge_count() {
+        let input = b"ab";
+        let (_, x) = seq_small::<[u8; 4], SeqError<ByteError>, _>(usize::MAX, byte)(input);
+        assert_eq!(x.unwrap_err().step, 2);
+    }
 }
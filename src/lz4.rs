@@ -0,0 +1,216 @@
+use crate::{take, u32l, InputDebug, Step, TakeError, U32LError};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+use lz4_flex::block::{decompress_into, decompress_into_with_dict, DecompressError};
+
+const FRAME_MAGIC: u32 = 0x184d2204;
+const WINDOW_SIZE: usize = 64 * 1024;
+
+/// Not `Clone`, as the decompressor's error isn't.
+pub struct Lz4Error<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// The decompressor's error, or `None` if the error was in the framing
+    pub decompress_error: Option<DecompressError>,
+}
+
+impl<'a> Debug for Lz4Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Lz4Error")
+            .field("at", &InputDebug(self.at))
+            .field("decompress_error", &self.decompress_error)
+            .finish()
+    }
+}
+
+/// Decompressor errors are compared by kind, as they don't implement `PartialEq`.
+impl<'a> PartialEq for Lz4Error<'a> {
+    fn eq(&self, other: &Self) -> bool {
+        let kind = |x: &Self| x.decompress_error.as_ref().map(core::mem::discriminant);
+        self.at == other.at && kind(self) == kind(other)
+    }
+}
+
+impl<'a> Display for Lz4Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match &self.decompress_error {
+            Some(e) => write!(fmt, "LZ4 decompression failed: {}", e),
+            None => write!(fmt, "invalid LZ4 frame"),
+        }
+    }
+}
+
+impl<'a> From<TakeError<'a>> for Lz4Error<'a> {
+    fn from(x: TakeError<'a>) -> Self {
+        Self {
+            at: x.0,
+            decompress_error: None,
+        }
+    }
+}
+
+impl<'a> From<U32LError<'a>> for Lz4Error<'a> {
+    fn from(x: U32LError<'a>) -> Self {
+        Self {
+            at: x.0,
+            decompress_error: None,
+        }
+    }
+}
+
+fn framing_error(at: &[u8]) -> Lz4Error<'_> {
+    Lz4Error {
+        at,
+        decompress_error: None,
+    }
+}
+
+/// Decompress an LZ4 block of `compressed_len` bytes into at most `decompressed_len` bytes, then
+/// run a parser over the decompressed bytes. A `decompressed_len` more than 255 times
+/// `compressed_len` can't be right, so it's rejected before allocating. **Requires the `lz4`
+/// feature**
+pub fn lz4_block<'a, Output, Error: From<Lz4Error<'a>> + From<ChildError>, ChildError>(
+    compressed_len: usize,
+    decompressed_len: usize,
+    child: impl for<'b> Fn(&'b [u8]) -> Step<'b, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| {
+        let (rest, block) = match take::<Lz4Error>(compressed_len)(input) {
+            (rest, Ok(x)) => (rest, x),
+            (_, Err(e)) => return (input, Err(e.into())),
+        };
+        // The length usually comes from the input, and LZ4 expands by at most 255 times
+        if decompressed_len > compressed_len.saturating_mul(255) {
+            return (input, Err(framing_error(input).into()));
+        }
+        let mut out = vec![0; decompressed_len];
+        match decompress_into(block, &mut out) {
+            Ok(len) => out.truncate(len),
+            Err(e) => {
+                let error = Lz4Error {
+                    at: input,
+                    decompress_error: Some(e),
+                };
+                return (input, Err(error.into()));
+            }
+        }
+        match (child)(&out).1 {
+            Ok(x) => (rest, Ok(x)),
+            Err(e) => (input, Err(e.into())),
+        }
+    }
+}
+
+/// Decompress an LZ4 frame, then run a parser over the decompressed bytes. Checksums are skipped
+/// without being verified. **Requires the `lz4` feature**
+pub fn lz4_frame<'a, Output, Error: From<Lz4Error<'a>> + From<ChildError>, ChildError>(
+    child: impl for<'b> Fn(&'b [u8]) -> Step<'b, Output, ChildError>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| match decompress_frame(input) {
+        Ok((rest, out)) => match (child)(&out).1 {
+            Ok(x) => (rest, Ok(x)),
+            Err(e) => (input, Err(e.into())),
+        },
+        Err(e) => (input, Err(e.into())),
+    }
+}
+
+fn decompress_frame(input: &[u8]) -> Result<(&[u8], Vec<u8>), Lz4Error<'_>> {
+    let (rest, magic) = u32l::<Lz4Error>(input);
+    if magic? != FRAME_MAGIC {
+        return Err(framing_error(input));
+    }
+    let (rest, descriptor) = take::<Lz4Error>(2)(rest);
+    let descriptor = descriptor?;
+    let (flags, block_descriptor) = (descriptor[0], descriptor[1]);
+    if flags >> 6 != 0b01 {
+        return Err(framing_error(input));
+    }
+    let independent = flags & 0b0010_0000 != 0;
+    let block_checksum = flags & 0b0001_0000 != 0;
+    let content_size = flags & 0b0000_1000 != 0;
+    let content_checksum = flags & 0b0000_0100 != 0;
+    let dict_id = flags & 0b0000_0001 != 0;
+    let max_block_size = match (block_descriptor >> 4) & 0b111 {
+        4 => 64 * 1024,
+        5 => 256 * 1024,
+        6 => 1024 * 1024,
+        7 => 4 * 1024 * 1024,
+        _ => return Err(framing_error(input)),
+    };
+    // Content size, dictionary ID, and header checksum.
+    let header_len = 8 * content_size as usize + 4 * dict_id as usize + 1;
+    let (mut rest, header) = take::<Lz4Error>(header_len)(rest);
+    header?;
+
+    let mut out = Vec::new();
+    let mut block = vec![0; max_block_size];
+    loop {
+        let at = rest;
+        let (next, size) = u32l::<Lz4Error>(rest);
+        let size = size?;
+        if size == 0 {
+            rest = next;
+            break;
+        }
+        let compressed = size & 0x8000_0000 == 0;
+        let (next, data) = take::<Lz4Error>((size & 0x7fff_ffff) as usize)(next);
+        let data = data?;
+        if !compressed {
+            out.extend_from_slice(data);
+        } else {
+            let dict = match independent {
+                true => &[][..],
+                false => &out[out.len().saturating_sub(WINDOW_SIZE)..],
+            };
+            match decompress_into_with_dict(data, &mut block, dict) {
+                Ok(len) => out.extend_from_slice(&block[..len]),
+                Err(e) => {
+                    return Err(Lz4Error {
+                        at,
+                        decompress_error: Some(e),
+                    })
+                }
+            }
+        }
+        let (next, checksum) = take::<Lz4Error>(4 * block_checksum as usize)(next);
+        checksum?;
+        rest = next;
+    }
+    let (rest, checksum) = take::<Lz4Error>(4 * content_checksum as usize)(rest);
+    checksum?;
+    Ok((rest, out))
+}
+
+std_error! {
+    Lz4Error<'a>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{rest, ErrorKind, ParseError, RestError};
+
+    fn all(input: &[u8]) -> Step<'_, Vec<u8>, RestError> {
+        let (rest, x) = rest(input);
+        (rest, x.map(|x| x.to_vec()))
+    }
+
+    #[test]
+    fn block_literals() {
+        // A token with 3 literals and no match
+        let block = [0x30, b'a', b'b', b'c'];
+        let (rest, x) = lz4_block::<_, ParseError, _>(4, 16, all)(&block);
+        assert_eq!(rest, &[]);
+        assert_eq!(x.unwrap(), b"abc");
+    }
+
+    #[test]
+    fn block_rejects_impossible_length() {
+        let block = [0x30, b'a', b'b', b'c'];
+        let (rest, x) = lz4_block::<_, ParseError, _>(4, usize::MAX, all)(&block);
+        assert_eq!(rest, &block);
+        assert_eq!(x.unwrap_err().kind, ErrorKind::Lz4);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::Step;
+use alloc::collections::BTreeMap;
+use core::cell::RefCell;
+use core::marker::PhantomData;
+
+/// Makes `'a` invariant, since it's both taken and returned. Otherwise the cache could outlive a
+/// buffer it was used on:
+///
+/// ```compile_fail
+/// use parz::{memo, Memo, Step};
+///
+/// fn len(input: &[u8]) -> Step<'_, usize, ()> {
+///     (&input[input.len()..], Ok(input.len()))
+/// }
+///
+/// let cache = Memo::new();
+/// {
+///     let buffer = vec![1, 2];
+///     let _ = memo(&cache, len)(&buffer);
+/// }
+/// let buffer = vec![3];
+/// let _ = memo(&cache, len)(&buffer);
+/// ```
+type Invariant<'a> = fn(&'a [u8]) -> &'a [u8];
+
+/// Caches a parser's outputs by where in the input it ran, so a region referenced many times is
+/// only parsed once. Use a separate cache for each parser.
+pub struct Memo<'a, T> {
+    /// Outputs and how many bytes were consumed, keyed by the address and length of the input
+    entries: RefCell<BTreeMap<(usize, usize), (usize, T)>>,
+    /// Keeps the input borrowed so its addresses can't be reused by another buffer. Invariant, so
+    /// `'a` can't be shortened to let in a buffer that's freed sooner.
+    input: PhantomData<Invariant<'a>>,
+}
+
+impl<'a, T: Clone> Memo<'a, T> {
+    pub fn new() -> Self {
+        Self {
+            entries: RefCell::new(BTreeMap::new()),
+            input: PhantomData,
+        }
+    }
+
+    /// How many outputs are cached.
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear()
+    }
+}
+
+impl<'a, T: Clone> Default for Memo<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run a parser once per position, outputting a clone of the cached output after that. Errors
+/// aren't cached.
+pub fn memo<'a, 'b, Output: Clone, Error>(
+    cache: &'b Memo<'a, Output>,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, Error> + 'b,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> + 'b {
+    move |input| {
+        let key = (input.as_ptr() as usize, input.len());
+        if let Some((consumed, x)) = cache.entries.borrow().get(&key) {
+            return (&input[*consumed..], Ok(x.clone()));
+        }
+        let (rest, result) = (child)(input);
+        if let Ok(x) = &result {
+            let consumed = input.len() - rest.len();
+            cache
+                .entries
+                .borrow_mut()
+                .insert(key, (consumed, x.clone()));
+        }
+        (rest, result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    #[test]
+    fn memo_by_position() {
+        let runs = Cell::new(0);
+        let cache = Memo::new();
+        let parser = memo(&cache, |input: &[u8]| -> Step<'_, u8, ()> {
+            runs.set(runs.get() + 1);
+            (&input[1..], Ok(input[0]))
+        });
+        let input = b"ab";
+        assert_eq!(parser(input), (&input[1..], Ok(b'a')));
+        assert_eq!(parser(input), (&input[1..], Ok(b'a')));
+        assert_eq!(parser(&input[1..]), (&input[2..], Ok(b'b')));
+        assert_eq!(runs.get(), 2);
+        assert_eq!(cache.len(), 2);
+    }
+}
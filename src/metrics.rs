@@ -0,0 +1,120 @@
+use crate::Step;
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use core::cell::RefCell;
+
+/// Receives the outcome of each run of a labeled parser. The `()` sink ignores everything, so
+/// measuring with it compiles away.
+pub trait Metrics {
+    /// A parser succeeded, consuming some bytes.
+    fn success(&self, label: &'static str, consumed: usize);
+
+    /// A parser failed.
+    fn failure(&self, label: &'static str);
+}
+
+impl Metrics for () {
+    #[inline(always)]
+    fn success(&self, _label: &'static str, _consumed: usize) {}
+
+    #[inline(always)]
+    fn failure(&self, _label: &'static str) {}
+}
+
+/// Totals for one labeled parser.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParserStats {
+    pub attempts: u64,
+    pub successes: u64,
+    pub failures: u64,
+    /// Bytes consumed by successful runs
+    pub consumed: u64,
+}
+
+/// A [`Metrics`] sink that totals runs for each label. **Requires the `alloc` feature**
+#[cfg(feature = "alloc")]
+#[derive(Default)]
+pub struct StatsMap {
+    stats: RefCell<BTreeMap<&'static str, ParserStats>>,
+}
+
+#[cfg(feature = "alloc")]
+impl StatsMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the totals for a label.
+    pub fn get(&self, label: &str) -> Option<ParserStats> {
+        self.stats.borrow().get(label).copied()
+    }
+
+    /// Take the totals for every label, leaving the map empty.
+    pub fn take(&self) -> BTreeMap<&'static str, ParserStats> {
+        core::mem::take(&mut *self.stats.borrow_mut())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Metrics for StatsMap {
+    fn success(&self, label: &'static str, consumed: usize) {
+        let mut stats = self.stats.borrow_mut();
+        let x = stats.entry(label).or_default();
+        x.attempts += 1;
+        x.successes += 1;
+        x.consumed += consumed as u64;
+    }
+
+    fn failure(&self, label: &'static str) {
+        let mut stats = self.stats.borrow_mut();
+        let x = stats.entry(label).or_default();
+        x.attempts += 1;
+        x.failures += 1;
+    }
+}
+
+/// Report each run of a parser to a [`Metrics`] sink under a label.
+pub fn measured<'a, 'b, Output, Error, M: Metrics>(
+    sink: &'b M,
+    label: &'static str,
+    child: impl Fn(&'a [u8]) -> Step<'a, Output, Error> + 'b,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> + 'b {
+    move |input| {
+        let (rest, result) = (child)(input);
+        match result {
+            Ok(_) => sink.success(label, input.len() - rest.len()),
+            Err(_) => sink.failure(label),
+        }
+        (rest, result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{u16l, U16LError};
+
+    #[test]
+    fn unit_sink_passes_through() {
+        let (rest, x) = measured(&(), "u16", u16l::<U16LError>)(&[1, 0, 2]);
+        assert_eq!(rest, &[2]);
+        assert_eq!(x.unwrap(), 1);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn stats_map_totals() {
+        let stats = StatsMap::new();
+        let parser = measured(&stats, "u16", u16l::<U16LError>);
+        let _ = parser(&[1, 0]);
+        let _ = parser(&[1]);
+        let expected = ParserStats {
+            attempts: 2,
+            successes: 1,
+            failures: 1,
+            consumed: 2,
+        };
+        assert_eq!(stats.get("u16"), Some(expected));
+    }
+}
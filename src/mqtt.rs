@@ -0,0 +1,457 @@
+//! MQTT control packet parsers, for versions 3.1.1 and 5.
+
+use crate::{take, InputDebug, Step, TakeError};
+use core::fmt::{self, Debug, Display, Formatter};
+use core::str;
+
+#[derive(Clone, PartialEq)]
+pub struct MqttError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for MqttError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("MqttError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for MqttError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid MQTT packet")
+    }
+}
+
+impl<'a> From<TakeError<'a>> for MqttError<'a> {
+    fn from(x: TakeError<'a>) -> Self {
+        Self(x.0)
+    }
+}
+
+/// A version of the protocol.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Version {
+    V311,
+    V5,
+}
+
+pub const CONNECT: u8 = 1;
+pub const CONNACK: u8 = 2;
+pub const PUBLISH: u8 = 3;
+pub const PUBACK: u8 = 4;
+pub const PUBREC: u8 = 5;
+pub const PUBREL: u8 = 6;
+pub const PUBCOMP: u8 = 7;
+pub const SUBSCRIBE: u8 = 8;
+pub const SUBACK: u8 = 9;
+pub const UNSUBSCRIBE: u8 = 10;
+pub const UNSUBACK: u8 = 11;
+pub const PINGREQ: u8 = 12;
+pub const PINGRESP: u8 = 13;
+pub const DISCONNECT: u8 = 14;
+pub const AUTH: u8 = 15;
+
+fn fail<'a, Output, Error: From<MqttError<'a>>>(
+    input: &'a [u8],
+    at: &'a [u8],
+) -> Step<'a, Output, Error> {
+    (input, Err(MqttError(at).into()))
+}
+
+/// Parse a variable byte integer, as used for the remaining length.
+pub fn varint<'a, Error: From<MqttError<'a>>>(input: &'a [u8]) -> Step<'a, u32, Error> {
+    let mut out = 0;
+    for (i, &b) in input.iter().enumerate().take(4) {
+        out |= ((b & 0x7f) as u32) << (7 * i);
+        if b & 0x80 == 0 {
+            // The shortest encoding must be used
+            return match b == 0 && i != 0 {
+                true => fail(input, &input[i..]),
+                false => (&input[i + 1..], Ok(out)),
+            };
+        }
+    }
+    fail(input, input)
+}
+
+fn u16b<'a>(input: &'a [u8]) -> Step<'a, u16, MqttError<'a>> {
+    match take::<MqttError>(2)(input) {
+        (rest, Ok(x)) => (rest, Ok(u16::from_be_bytes([x[0], x[1]]))),
+        (_, Err(e)) => (input, Err(e)),
+    }
+}
+
+fn u32b<'a>(input: &'a [u8]) -> Step<'a, u32, MqttError<'a>> {
+    match take::<MqttError>(4)(input) {
+        (rest, Ok(x)) => (rest, Ok(u32::from_be_bytes([x[0], x[1], x[2], x[3]]))),
+        (_, Err(e)) => (input, Err(e)),
+    }
+}
+
+fn byte<'a>(input: &'a [u8]) -> Step<'a, u8, MqttError<'a>> {
+    match input.split_first() {
+        Some((&b, rest)) => (rest, Ok(b)),
+        None => fail(input, input),
+    }
+}
+
+/// Parse binary data prefixed with its 16-bit length.
+pub fn binary<'a, Error: From<MqttError<'a>>>(input: &'a [u8]) -> Step<'a, &'a [u8], Error> {
+    let (rest, len) = step!(input, u16b(input));
+    let (rest, x) = step!(input, take::<MqttError>(len as usize)(rest));
+    (rest, Ok(x))
+}
+
+/// Parse a UTF-8 string prefixed with its 16-bit length, rejecting null characters.
+pub fn string<'a, Error: From<MqttError<'a>>>(input: &'a [u8]) -> Step<'a, &'a str, Error> {
+    let (rest, bytes) = step!(input, binary::<MqttError>(input));
+    match str::from_utf8(bytes) {
+        Ok(x) if !x.contains('\0') => (rest, Ok(x)),
+        _ => fail(input, input),
+    }
+}
+
+/// The fixed header at the start of every packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FixedHeader {
+    /// The packet type, like [`PUBLISH`]
+    pub kind: u8,
+    /// The flags in the low four bits of the first byte
+    pub flags: u8,
+    /// Length of the rest of the packet
+    pub remaining_len: u32,
+}
+
+/// Parse the fixed header of a packet, rejecting reserved packet types and invalid flags.
+pub fn fixed_header<'a, Error: From<MqttError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, FixedHeader, Error> {
+    let (rest, first) = step!(input, byte(input));
+    let (kind, flags) = (first >> 4, first & 0x0f);
+    let valid = match kind {
+        0 => false,
+        PUBLISH => (flags >> 1) & 0b11 != 0b11,
+        PUBREL | SUBSCRIBE | UNSUBSCRIBE => flags == 0b0010,
+        _ => flags == 0,
+    };
+    if !valid {
+        return fail(input, input);
+    }
+    let (rest, remaining_len) = step!(input, varint::<MqttError>(rest));
+    let header = FixedHeader {
+        kind,
+        flags,
+        remaining_len,
+    };
+    (rest, Ok(header))
+}
+
+/// The value of a property.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PropertyValue<'a> {
+    Byte(u8),
+    U16(u16),
+    U32(u32),
+    Varint(u32),
+    String(&'a str),
+    Binary(&'a [u8]),
+    Pair(&'a str, &'a str),
+}
+
+/// A property of an MQTT 5 packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Property<'a> {
+    /// The property identifier, like `0x26` for a user property
+    pub id: u8,
+    pub value: PropertyValue<'a>,
+}
+
+fn property<'a>(input: &'a [u8]) -> Step<'a, Property<'a>, MqttError<'a>> {
+    let (rest, id) = step!(input, varint::<MqttError>(input));
+    let (rest, value) = match id {
+        0x01 | 0x17 | 0x19 | 0x24 | 0x25 | 0x28 | 0x29 | 0x2a => {
+            let (rest, x) = step!(input, byte(rest));
+            (rest, PropertyValue::Byte(x))
+        }
+        0x13 | 0x21 | 0x22 | 0x23 => {
+            let (rest, x) = step!(input, u16b(rest));
+            (rest, PropertyValue::U16(x))
+        }
+        0x02 | 0x11 | 0x18 | 0x27 => {
+            let (rest, x) = step!(input, u32b(rest));
+            (rest, PropertyValue::U32(x))
+        }
+        0x0b => {
+            let (rest, x) = step!(input, varint::<MqttError>(rest));
+            (rest, PropertyValue::Varint(x))
+        }
+        0x03 | 0x08 | 0x12 | 0x15 | 0x1a | 0x1c | 0x1f => {
+            let (rest, x) = step!(input, string::<MqttError>(rest));
+            (rest, PropertyValue::String(x))
+        }
+        0x09 | 0x16 => {
+            let (rest, x) = step!(input, binary::<MqttError>(rest));
+            (rest, PropertyValue::Binary(x))
+        }
+        0x26 => {
+            let (rest, key) = step!(input, string::<MqttError>(rest));
+            let (rest, value) = step!(input, string::<MqttError>(rest));
+            (rest, PropertyValue::Pair(key, value))
+        }
+        _ => return fail(input, input),
+    };
+    let id = id as u8;
+    (rest, Ok(Property { id, value }))
+}
+
+/// The properties of an MQTT 5 packet, which can be iterated without allocating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Properties<'a>(
+    /// The encoded properties, without their length
+    pub &'a [u8],
+);
+
+impl<'a> Iterator for Properties<'a> {
+    type Item = Property<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The properties were validated when they were parsed.
+        let (rest, x) = property(self.0);
+        self.0 = rest;
+        x.ok()
+    }
+}
+
+/// Parse the properties of an MQTT 5 packet, prefixed with their length.
+pub fn properties<'a, Error: From<MqttError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, Properties<'a>, Error> {
+    let (rest, len) = step!(input, varint::<MqttError>(input));
+    let (rest, mut bytes) = step!(input, take::<MqttError>(len as usize)(rest));
+    let properties = Properties(bytes);
+    while !bytes.is_empty() {
+        bytes = match property(bytes) {
+            (next, Ok(_)) => next,
+            (_, Err(e)) => return (input, Err(e.into())),
+        };
+    }
+    (rest, Ok(properties))
+}
+
+fn properties_for<'a>(
+    version: Version,
+    input: &'a [u8],
+) -> Step<'a, Option<Properties<'a>>, MqttError<'a>> {
+    match version {
+        Version::V311 => (input, Ok(None)),
+        Version::V5 => match properties::<MqttError>(input) {
+            (rest, Ok(x)) => (rest, Ok(Some(x))),
+            (rest, Err(e)) => (rest, Err(e)),
+        },
+    }
+}
+
+/// The variable header of a CONNECT packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Connect<'a> {
+    pub version: Version,
+    /// The connect flags, like whether there's a username or password
+    pub flags: u8,
+    /// The keep alive interval in seconds
+    pub keep_alive: u16,
+    /// Only for MQTT 5
+    pub properties: Option<Properties<'a>>,
+}
+
+/// Parse the variable header of a CONNECT packet, which says the version for the rest of the
+/// connection.
+pub fn connect<'a, Error: From<MqttError<'a>>>(input: &'a [u8]) -> Step<'a, Connect<'a>, Error> {
+    let (rest, name) = step!(input, string::<MqttError>(input));
+    let (next, level) = step!(input, byte(rest));
+    let version = match (name, level) {
+        ("MQTT", 4) => Version::V311,
+        ("MQTT", 5) => Version::V5,
+        _ => return fail(input, input),
+    };
+    let (next, flags) = step!(input, byte(next));
+    if flags & 1 != 0 {
+        return fail(input, &rest[1..]);
+    }
+    let (rest, keep_alive) = step!(input, u16b(next));
+    let (rest, properties) = step!(input, properties_for(version, rest));
+    let connect = Connect {
+        version,
+        flags,
+        keep_alive,
+        properties,
+    };
+    (rest, Ok(connect))
+}
+
+/// The variable header of a CONNACK packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Connack<'a> {
+    pub session_present: bool,
+    /// The return code for MQTT 3.1.1, or the reason code for MQTT 5
+    pub reason: u8,
+    /// Only for MQTT 5
+    pub properties: Option<Properties<'a>>,
+}
+
+/// Parse the variable header of a CONNACK packet.
+pub fn connack<'a, Error: From<MqttError<'a>>>(
+    version: Version,
+) -> impl Fn(&'a [u8]) -> Step<'a, Connack<'a>, Error> {
+    move |input| {
+        let (rest, flags) = step!(input, byte(input));
+        if flags & !1 != 0 {
+            return fail(input, input);
+        }
+        let (rest, reason) = step!(input, byte(rest));
+        let (rest, properties) = step!(input, properties_for(version, rest));
+        let connack = Connack {
+            session_present: flags & 1 != 0,
+            reason,
+            properties,
+        };
+        (rest, Ok(connack))
+    }
+}
+
+/// The variable header of a PUBLISH packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Publish<'a> {
+    pub topic: &'a str,
+    /// Only when the QoS is above 0
+    pub packet_id: Option<u16>,
+    /// Only for MQTT 5
+    pub properties: Option<Properties<'a>>,
+}
+
+/// Parse the variable header of a PUBLISH packet, using the flags from its fixed header.
+pub fn publish<'a, Error: From<MqttError<'a>>>(
+    version: Version,
+    flags: u8,
+) -> impl Fn(&'a [u8]) -> Step<'a, Publish<'a>, Error> {
+    move |input| {
+        let (rest, topic) = step!(input, string::<MqttError>(input));
+        let (rest, packet_id) = match (flags >> 1) & 0b11 {
+            0 => (rest, None),
+            _ => {
+                let (rest, x) = step!(input, u16b(rest));
+                (rest, Some(x))
+            }
+        };
+        let (rest, properties) = step!(input, properties_for(version, rest));
+        let publish = Publish {
+            topic,
+            packet_id,
+            properties,
+        };
+        (rest, Ok(publish))
+    }
+}
+
+/// The variable header of a PUBACK, PUBREC, PUBREL, or PUBCOMP packet. UNSUBACK packets are
+/// parsed with [`suback`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ack<'a> {
+    pub packet_id: u16,
+    /// The reason code, which is always 0 for MQTT 3.1.1
+    pub reason: u8,
+    /// Only for MQTT 5, and empty when left out of the packet
+    pub properties: Option<Properties<'a>>,
+}
+
+/// Parse the variable header of an acknowledgement packet, using the remaining length from its
+/// fixed header. For MQTT 5, the reason code and properties may be left out.
+pub fn ack<'a, Error: From<MqttError<'a>>>(
+    version: Version,
+    remaining_len: u32,
+) -> impl Fn(&'a [u8]) -> Step<'a, Ack<'a>, Error> {
+    move |input| {
+        let (rest, packet_id) = step!(input, u16b(input));
+        let (rest, reason, properties) = match (version, remaining_len) {
+            (Version::V311, _) => (rest, 0, None),
+            (Version::V5, 2) => (rest, 0, Some(Properties(&[]))),
+            (Version::V5, 3) => {
+                let (rest, reason) = step!(input, byte(rest));
+                (rest, reason, Some(Properties(&[])))
+            }
+            (Version::V5, _) => {
+                let (rest, reason) = step!(input, byte(rest));
+                let (rest, properties) = step!(input, properties::<MqttError>(rest));
+                (rest, reason, Some(properties))
+            }
+        };
+        let ack = Ack {
+            packet_id,
+            reason,
+            properties,
+        };
+        (rest, Ok(ack))
+    }
+}
+
+/// A SUBACK or UNSUBACK packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Suback<'a> {
+    pub packet_id: u16,
+    /// Only for MQTT 5
+    pub properties: Option<Properties<'a>>,
+    /// A return code or reason code for each subscription, or for UNSUBACK a reason code for each
+    /// topic filter, which is empty for MQTT 3.1.1
+    pub reasons: &'a [u8],
+}
+
+/// Parse the variable header and payload of a SUBACK or UNSUBACK packet, using the remaining
+/// length from its fixed header. Both are a packet identifier, properties for MQTT 5, and then
+/// the reason codes.
+pub fn suback<'a, Error: From<MqttError<'a>>>(
+    version: Version,
+    remaining_len: u32,
+) -> impl Fn(&'a [u8]) -> Step<'a, Suback<'a>, Error> {
+    move |input| {
+        let (rest, packet) = step!(input, take::<MqttError>(remaining_len as usize)(input));
+        let (next, packet_id) = step!(input, u16b(packet));
+        let (next, properties) = step!(input, properties_for(version, next));
+        let suback = Suback {
+            packet_id,
+            properties,
+            reasons: next,
+        };
+        (rest, Ok(suback))
+    }
+}
+
+std_error! {
+    MqttError<'a>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsuback_v5_reason_codes() {
+        // Packet identifier, empty properties, then a reason code for each topic filter
+        let packet = [0x00, 0x07, 0x00, 0x00, 0x11];
+        let (rest, x) = suback::<MqttError>(Version::V5, 5)(&packet);
+        let x = x.unwrap();
+        assert_eq!(rest, &[]);
+        assert_eq!(x.packet_id, 7);
+        assert_eq!(x.properties, Some(Properties(&[])));
+        assert_eq!(x.reasons, &[0x00, 0x11]);
+    }
+
+    #[test]
+    fn unsuback_v311_has_no_reason_codes() {
+        let (rest, x) = suback::<MqttError>(Version::V311, 2)(&[0x00, 0x07]);
+        let x = x.unwrap();
+        assert_eq!(rest, &[]);
+        assert_eq!(x.packet_id, 7);
+        assert_eq!(x.reasons, &[]);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{Either, OptError, OrError, Recoverable, Step, TakeError};
+use core::marker::PhantomData;
+
+/// A parser, implemented for every function from input to a [`Step`], with methods for chaining
+/// parsers together instead of nesting them.
+///
+/// The methods output adapters that implement `Parser` but not `Fn`. To pass one to the other
+/// combinators, wrap it in a closure like `|input| parser.parse(input)`.
+pub trait Parser<'a, Output, Error> {
+    fn parse(&self, input: &'a [u8]) -> Step<'a, Output, Error>;
+
+    /// Convert the output with a function.
+    fn map<To, F: Fn(Output) -> To>(self, map: F) -> Map<Self, F, Output>
+    where
+        Self: Sized,
+    {
+        Map {
+            child: self,
+            map,
+            types: PhantomData,
+        }
+    }
+
+    /// Run another parser after this one, where both must succeed. See [`and`](crate::and).
+    fn and<To, Other, OtherOutput, OtherError>(
+        self,
+        other: Other,
+    ) -> And<Self, Other, (Error, OtherError, To)>
+    where
+        Self: Sized,
+        Other: Parser<'a, OtherOutput, OtherError>,
+        To: From<Error> + From<OtherError>,
+    {
+        And {
+            one: self,
+            two: other,
+            types: PhantomData,
+        }
+    }
+
+    /// Try another parser if this one fails. See [`or`](crate::or).
+    fn or<To, Other, OtherOutput, OtherError>(
+        self,
+        other: Other,
+    ) -> Or<Self, Other, (Error, OtherError, To)>
+    where
+        Self: Sized,
+        Other: Parser<'a, OtherOutput, OtherError>,
+        Error: Recoverable,
+        OtherError: Recoverable,
+        To: From<OrError<'a, Error, OtherError>> + From<Error> + From<OtherError>,
+    {
+        Or {
+            one: self,
+            two: other,
+            types: PhantomData,
+        }
+    }
+
+    /// Allow this parser to fail. See [`opt`](crate::opt).
+    fn opt(self) -> Opt<Self, Error>
+    where
+        Self: Sized,
+    {
+        Opt {
+            child: self,
+            types: PhantomData,
+        }
+    }
+
+    /// Take N bytes after this parser, outputting them along with its output.
+    fn then_take<To>(self, count: usize) -> ThenTake<Self, (Error, To)>
+    where
+        Self: Sized,
+        To: From<Error> + From<TakeError<'a>>,
+    {
+        ThenTake {
+            child: self,
+            count,
+            types: PhantomData,
+        }
+    }
+}
+
+impl<'a, Output, Error, F: Fn(&'a [u8]) -> Step<'a, Output, Error>> Parser<'a, Output, Error>
+    for F
+{
+    fn parse(&self, input: &'a [u8]) -> Step<'a, Output, Error> {
+        (self)(input)
+    }
+}
+
+/// Parser from [`Parser::map`].
+pub struct Map<P, F, T> {
+    child: P,
+    map: F,
+    types: PhantomData<fn() -> T>,
+}
+
+impl<'a, Output, Error, To, P, F> Parser<'a, To, Error> for Map<P, F, Output>
+where
+    P: Parser<'a, Output, Error>,
+    F: Fn(Output) -> To,
+{
+    fn parse(&self, input: &'a [u8]) -> Step<'a, To, Error> {
+        let (rest, result) = self.child.parse(input);
+        (rest, result.map(&self.map))
+    }
+}
+
+/// Parser from [`Parser::and`].
+pub struct And<A, B, T> {
+    one: A,
+    two: B,
+    types: PhantomData<fn() -> T>,
+}
+
+impl<'a, Output1, Output2, Error1, Error2, To, A, B> Parser<'a, (Output1, Output2), To>
+    for And<A, B, (Error1, Error2, To)>
+where
+    A: Parser<'a, Output1, Error1>,
+    B: Parser<'a, Output2, Error2>,
+    To: From<Error1> + From<Error2>,
+{
+    fn parse(&self, input: &'a [u8]) -> Step<'a, (Output1, Output2), To> {
+        crate::and(|x| self.one.parse(x), |x| self.two.parse(x))(input)
+    }
+}
+
+/// Parser from [`Parser::or`].
+pub struct Or<A, B, T> {
+    one: A,
+    two: B,
+    types: PhantomData<fn() -> T>,
+}
+
+impl<'a, Output1, Output2, Error1, Error2, To, A, B> Parser<'a, Either<Output1, Output2>, To>
+    for Or<A, B, (Error1, Error2, To)>
+where
+    A: Parser<'a, Output1, Error1>,
+    B: Parser<'a, Output2, Error2>,
+    Error1: Recoverable,
+    Error2: Recoverable,
+    To: From<OrError<'a, Error1, Error2>> + From<Error1> + From<Error2>,
+{
+    fn parse(&self, input: &'a [u8]) -> Step<'a, Either<Output1, Output2>, To> {
+        crate::or(|x| self.one.parse(x), |x| self.two.parse(x))(input)
+    }
+}
+
+/// Parser from [`Parser::opt`].
+pub struct Opt<P, T> {
+    child: P,
+    types: PhantomData<fn() -> T>,
+}
+
+impl<'a, Output, Error, P: Parser<'a, Output, Error>> Parser<'a, Option<Output>, OptError>
+    for Opt<P, Error>
+{
+    fn parse(&self, input: &'a [u8]) -> Step<'a, Option<Output>, OptError> {
+        crate::opt(|x| self.child.parse(x))(input)
+    }
+}
+
+/// Parser from [`Parser::then_take`].
+pub struct ThenTake<P, T> {
+    child: P,
+    count: usize,
+    types: PhantomData<fn() -> T>,
+}
+
+impl<'a, Output, Error, To, P> Parser<'a, (Output, &'a [u8]), To> for ThenTake<P, (Error, To)>
+where
+    P: Parser<'a, Output, Error>,
+    To: From<Error> + From<TakeError<'a>>,
+{
+    fn parse(&self, input: &'a [u8]) -> Step<'a, (Output, &'a [u8]), To> {
+        crate::and(
+            |x| self.child.parse(x),
+            crate::take::<TakeError>(self.count),
+        )(input)
+    }
+}
@@ -0,0 +1,213 @@
+//! PCAP and PCAP-NG capture file parsers.
+
+use crate::{take, InputDebug, Step, TakeError};
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct PcapError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for PcapError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("PcapError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for PcapError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid capture data")
+    }
+}
+
+impl<'a> From<TakeError<'a>> for PcapError<'a> {
+    fn from(x: TakeError<'a>) -> Self {
+        Self(x.0)
+    }
+}
+
+const MICROSECOND_MAGIC: u32 = 0xa1b2c3d4;
+const NANOSECOND_MAGIC: u32 = 0xa1b23c4d;
+const SECTION_HEADER: u32 = 0x0a0d0d0a;
+const BYTE_ORDER_MAGIC: u32 = 0x1a2b3c4d;
+
+fn uint16<'a>(big_endian: bool, input: &'a [u8]) -> Step<'a, u16, PcapError<'a>> {
+    let (rest, x) = step!(input, take::<PcapError>(2)(input));
+    let x = [x[0], x[1]];
+    let x = match big_endian {
+        true => u16::from_be_bytes(x),
+        false => u16::from_le_bytes(x),
+    };
+    (rest, Ok(x))
+}
+
+fn uint32<'a>(big_endian: bool, input: &'a [u8]) -> Step<'a, u32, PcapError<'a>> {
+    let (rest, x) = step!(input, take::<PcapError>(4)(input));
+    let x = [x[0], x[1], x[2], x[3]];
+    let x = match big_endian {
+        true => u32::from_be_bytes(x),
+        false => u32::from_le_bytes(x),
+    };
+    (rest, Ok(x))
+}
+
+/// The header at the start of a PCAP file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GlobalHeader {
+    /// Whether the file's numbers are big-endian, as detected from the magic number
+    pub big_endian: bool,
+    /// Whether timestamps have nanoseconds instead of microseconds
+    pub nanosecond: bool,
+    pub version_major: u16,
+    pub version_minor: u16,
+    /// The most bytes captured from each packet
+    pub snaplen: u32,
+    /// The link-layer header type, like 1 for Ethernet
+    pub link_type: u32,
+}
+
+/// Parse the header of a PCAP file, detecting its endianness and timestamp resolution.
+pub fn global_header<'a, Error: From<PcapError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, GlobalHeader, Error> {
+    let (rest, magic) = step!(input, uint32(false, input));
+    let (big_endian, nanosecond) = match (magic, magic.swap_bytes()) {
+        (MICROSECOND_MAGIC, _) => (false, false),
+        (NANOSECOND_MAGIC, _) => (false, true),
+        (_, MICROSECOND_MAGIC) => (true, false),
+        (_, NANOSECOND_MAGIC) => (true, true),
+        _ => return (input, Err(PcapError(input).into())),
+    };
+    let (rest, version_major) = step!(input, uint16(big_endian, rest));
+    let (rest, version_minor) = step!(input, uint16(big_endian, rest));
+    // The time zone offset and timestamp accuracy, which are always 0
+    let (rest, _) = step!(input, take::<PcapError>(8)(rest));
+    let (rest, snaplen) = step!(input, uint32(big_endian, rest));
+    let (rest, link_type) = step!(input, uint32(big_endian, rest));
+    let header = GlobalHeader {
+        big_endian,
+        nanosecond,
+        version_major,
+        version_minor,
+        snaplen,
+        link_type,
+    };
+    (rest, Ok(header))
+}
+
+/// A packet of a PCAP file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Packet<'a> {
+    pub seconds: u32,
+    /// Microseconds or nanoseconds, depending on [`GlobalHeader::nanosecond`]
+    pub fraction: u32,
+    /// Length of the packet on the wire, which may be more than was captured
+    pub orig_len: u32,
+    /// The captured bytes of the packet
+    pub data: &'a [u8],
+}
+
+/// Parse a packet record of a PCAP file with the given header.
+pub fn packet<'a, Error: From<PcapError<'a>>>(
+    header: &GlobalHeader,
+) -> impl Fn(&'a [u8]) -> Step<'a, Packet<'a>, Error> {
+    let big_endian = header.big_endian;
+    move |input| {
+        let (rest, seconds) = step!(input, uint32(big_endian, input));
+        let (rest, fraction) = step!(input, uint32(big_endian, rest));
+        let (rest, incl_len) = step!(input, uint32(big_endian, rest));
+        let (rest, orig_len) = step!(input, uint32(big_endian, rest));
+        let (rest, data) = step!(input, take::<PcapError>(incl_len as usize)(rest));
+        let packet = Packet {
+            seconds,
+            fraction,
+            orig_len,
+            data,
+        };
+        (rest, Ok(packet))
+    }
+}
+
+/// A block of a PCAP-NG file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// The block type, like 6 for an enhanced packet block
+    pub kind: u32,
+    /// Whether the block's section is big-endian
+    pub big_endian: bool,
+    /// The block body, between the lengths
+    pub body: &'a [u8],
+}
+
+/// Parse a block of a PCAP-NG file, in a section of the given endianness. Section header blocks
+/// detect their own endianness from their byte-order magic.
+pub fn ng_block<'a, Error: From<PcapError<'a>>>(
+    big_endian: bool,
+) -> impl Fn(&'a [u8]) -> Step<'a, Block<'a>, Error> {
+    move |input| {
+        let (rest, kind) = step!(input, uint32(big_endian, input));
+        let big_endian = match kind {
+            SECTION_HEADER => match uint32(false, rest.get(4..).unwrap_or_default()).1 {
+                Ok(BYTE_ORDER_MAGIC) => false,
+                Ok(x) if x.swap_bytes() == BYTE_ORDER_MAGIC => true,
+                _ => return (input, Err(PcapError(input).into())),
+            },
+            _ => big_endian,
+        };
+        let (rest, len) = step!(input, uint32(big_endian, rest));
+        let body_len = match (len as usize).checked_sub(12) {
+            Some(x) if len % 4 == 0 => x,
+            _ => return (input, Err(PcapError(input).into())),
+        };
+        let (rest, body) = step!(input, take::<PcapError>(body_len)(rest));
+        let (rest, trailing_len) = step!(input, uint32(big_endian, rest));
+        if trailing_len != len {
+            return (input, Err(PcapError(input).into()));
+        }
+        let block = Block {
+            kind,
+            big_endian,
+            body,
+        };
+        (rest, Ok(block))
+    }
+}
+
+/// An iterator of the blocks of a PCAP-NG file, following the endianness of each section.
+pub struct NgBlocks<'a> {
+    input: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> NgBlocks<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            big_endian: false,
+        }
+    }
+
+    /// The bytes left after the last block, which are not empty if a block was invalid.
+    pub fn rest(&self) -> &'a [u8] {
+        self.input
+    }
+}
+
+impl<'a> Iterator for NgBlocks<'a> {
+    type Item = Block<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (rest, block) = ng_block::<PcapError>(self.big_endian)(self.input);
+        let block = block.ok()?;
+        self.input = rest;
+        self.big_endian = block.big_endian;
+        Some(block)
+    }
+}
+
+std_error! {
+    PcapError<'a>;
+}
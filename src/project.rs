@@ -0,0 +1,101 @@
+use crate::{SeqError, Step};
+use alloc::vec::Vec;
+
+/// A tuple of functions that each extract a field from a record into its own column.
+pub trait Extract<Record> {
+    /// A tuple with a `Vec` for each function's output
+    type Columns;
+
+    fn with_capacity(&self, capacity: usize) -> Self::Columns;
+
+    fn push(&self, columns: &mut Self::Columns, record: &Record);
+}
+
+macro_rules! extract_impl {
+    ($($f:ident $t:ident $i:tt),+) => {
+        impl<Record, $($t, $f: Fn(&Record) -> $t),+> Extract<Record> for ($($f,)+) {
+            type Columns = ($(Vec<$t>,)+);
+
+            fn with_capacity(&self, capacity: usize) -> Self::Columns {
+                ($(Vec::<$t>::with_capacity(capacity),)+)
+            }
+
+            fn push(&self, columns: &mut Self::Columns, record: &Record) {
+                $(columns.$i.push((self.$i)(record));)+
+            }
+        }
+    };
+}
+
+extract_impl!(F0 T0 0);
+extract_impl!(F0 T0 0, F1 T1 1);
+extract_impl!(F0 T0 0, F1 T1 1, F2 T2 2);
+extract_impl!(F0 T0 0, F1 T1 1, F2 T2 2, F3 T3 3);
+extract_impl!(F0 T0 0, F1 T1 1, F2 T2 2, F3 T3 3, F4 T4 4);
+extract_impl!(F0 T0 0, F1 T1 1, F2 T2 2, F3 T3 3, F4 T4 4, F5 T5 5);
+
+/// Run a record parser N times, storing the fields picked out by each extractor in separate
+/// `Vec`s instead of keeping the records.
+pub fn project<
+    'a,
+    Record,
+    E: Extract<Record>,
+    Error: From<SeqError<'a, ChildError>>,
+    ChildError,
+>(
+    count: usize,
+    record: impl Fn(&'a [u8]) -> Step<'a, Record, ChildError>,
+    extractors: E,
+) -> impl Fn(&'a [u8]) -> Step<'a, E::Columns, Error> {
+    move |mut input| {
+        let before = input;
+        let mut columns = extractors.with_capacity(count.min(input.len()));
+        for step in 0..count {
+            let (rest, result) = (record)(input);
+            match result {
+                Ok(x) => extractors.push(&mut columns, &x),
+                Err(child_error) => {
+                    return (
+                        before,
+                        Err(SeqError {
+                            at: input,
+                            step,
+                            child_error,
+                        }
+                        .into()),
+                    )
+                }
+            }
+            input = rest;
+        }
+        (input, Ok(columns))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{byte, ByteError};
+    use alloc::vec;
+
+    #[test]
+    fn project_columns() {
+        let input = b"ab";
+        let (rest, x) =
+            project::<_, _, SeqError<ByteError>, _>(2, byte, (|&b: &u8| b, |&b: &u8| b == b'a'))(
+                input,
+            );
+        assert!(rest.is_empty());
+        assert_eq!(x.unwrap(), (vec![b'a', b'b'], vec![true, false]));
+    }
+
+    #[test]
+    fn project_huge_count() {
+        // Preallocating the count would overflow the capacity before the input runs out
+        let input = b"ab";
+        let (rest, x) =
+            project::<_, _, SeqError<ByteError>, _>(usize::MAX, byte, (|&b: &u8| b,))(input);
+        assert_eq!(rest, input);
+        assert_eq!(x.unwrap_err().step, 2);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::Step;
+
+/// A cursor over a buffer that parsers are run against in turn.
+pub struct Reader<'a> {
+    root: &'a [u8],
+    input: &'a [u8],
+}
+
+/// A saved position of a [`Reader`].
+#[derive(Clone, Copy)]
+pub struct Checkpoint<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    pub fn new(root: &'a [u8]) -> Self {
+        Self { root, input: root }
+    }
+
+    /// Run a parser at the current position, advancing past what it consumed.
+    pub fn parse<Output, Error>(
+        &mut self,
+        parser: impl Fn(&'a [u8]) -> Step<'a, Output, Error>,
+    ) -> Result<Output, Error> {
+        let (rest, result) = (parser)(self.input);
+        self.input = rest;
+        result
+    }
+
+    /// Save the current position.
+    pub fn checkpoint(&self) -> Checkpoint<'a> {
+        Checkpoint(self.input)
+    }
+
+    /// Return to a saved position.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the checkpoint is from a reader over a different buffer.
+    pub fn rewind(&mut self, checkpoint: Checkpoint<'a>) {
+        let root = self.root.as_ptr_range();
+        let at = checkpoint.0.as_ptr_range();
+        assert!(
+            root.start <= at.start && at.end == root.end,
+            "checkpoint is from a different buffer"
+        );
+        self.input = checkpoint.0;
+    }
+
+    /// Offset of the current position from the start of the buffer.
+    pub fn position(&self) -> usize {
+        self.root.len() - self.input.len()
+    }
+
+    /// The bytes left to parse.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.input
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{byte, ByteError};
+
+    #[test]
+    fn rewind_to_checkpoint() {
+        let mut reader = Reader::new(b"ab");
+        let checkpoint = reader.checkpoint();
+        assert_eq!(reader.parse(byte::<ByteError>), Ok(b'a'));
+        assert_eq!(reader.position(), 1);
+        reader.rewind(checkpoint);
+        assert_eq!(reader.position(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "checkpoint is from a different buffer")]
+    fn rewind_other_buffer() {
+        let buffer = b"abcdef";
+        let mut reader = Reader::new(&buffer[..2]);
+        let other = Reader::new(&buffer[3..]);
+        reader.rewind(other.checkpoint());
+    }
+}
@@ -0,0 +1,70 @@
+use crate::Located;
+use alloc::string::String;
+use core::fmt::{Display, Write};
+
+/// Bytes shown per line of the hex dump
+const WIDTH: usize = 16;
+
+/// Render an error as its message followed by a hex dump of the lines of `root` around where it
+/// happened, with a caret under the failing byte.
+///
+/// ```text
+/// error: tag didn't match, 5 bytes left
+///  --> offset 4 (0x4)
+/// 00000000  52 49 46 46 66 6d 74 58  01                      |RIFFfmtX.|
+///                       ^^
+/// ```
+pub fn render_error<'a, Error: Located<'a> + Display>(root: &[u8], error: &Error) -> String {
+    let mut out = String::new();
+    // Writing to a String can't fail
+    let _ = render_into(&mut out, root, error);
+    out
+}
+
+fn render_into<'a, Error: Located<'a> + Display>(
+    out: &mut String,
+    root: &[u8],
+    error: &Error,
+) -> core::fmt::Result {
+    writeln!(out, "error: {}", error)?;
+    let offset = match error.offset_in(root) {
+        Some(x) => x,
+        None => return writeln!(out, " --> not in the input"),
+    };
+    writeln!(out, " --> offset {} ({:#x})", offset, offset)?;
+    let line = offset / WIDTH;
+    let last = match root.len() {
+        0 => 0,
+        len => (len - 1) / WIDTH,
+    };
+    for i in line.saturating_sub(1)..=(line + 1).min(last.max(line)) {
+        let start = i * WIDTH;
+        let bytes = root
+            .get(start..root.len().min(start + WIDTH))
+            .unwrap_or(&[]);
+        write!(out, "{:08x} ", start)?;
+        for j in 0..WIDTH {
+            if j % 8 == 0 {
+                out.push(' ');
+            }
+            match bytes.get(j) {
+                Some(b) => write!(out, "{:02x} ", b)?,
+                None => out.push_str("   "),
+            }
+        }
+        out.push('|');
+        for &b in bytes {
+            out.push(match b.is_ascii_graphic() || b == b' ' {
+                true => b as char,
+                false => '.',
+            });
+        }
+        out.push_str("|\n");
+        if i == line {
+            let column = offset % WIDTH;
+            let indent = 10 + column * 3 + column / 8;
+            writeln!(out, "{:indent$}^^", "", indent = indent)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use crate::{InputDebug, Step};
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct TimestampError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// Offset of the invalid character from where the error happened
+    pub offset: usize,
+}
+
+impl<'a> Debug for TimestampError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("TimestampError")
+            .field("at", &InputDebug(self.at))
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+impl<'a> Display for TimestampError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid timestamp at offset {}", self.offset)
+    }
+}
+
+/// A calendar date.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Date {
+    pub year: u16,
+    /// From 1 to 12
+    pub month: u8,
+    /// From 1 to 31
+    pub day: u8,
+}
+
+/// A date and time of day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timestamp {
+    pub date: Date,
+    /// From 0 to 23
+    pub hour: u8,
+    /// From 0 to 59
+    pub minute: u8,
+    /// From 0 to 60, allowing for leap seconds
+    pub second: u8,
+    pub nanosecond: u32,
+    /// Minutes east of UTC, or `None` for local time
+    pub offset: Option<i16>,
+}
+
+/// Reads fields from the text of a timestamp, remembering the offset of the first bad one.
+struct Fields<'a> {
+    text: &'a [u8],
+    at: usize,
+}
+
+impl<'a> Fields<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.text.get(self.at).copied()
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        let found = self.peek() == Some(c);
+        self.at += found as usize;
+        found
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), usize> {
+        match self.eat(c) {
+            true => Ok(()),
+            false => Err(self.at),
+        }
+    }
+
+    /// Read a number of N digits within a range.
+    fn number(&mut self, digits: usize, min: u32, max: u32) -> Result<u32, usize> {
+        let start = self.at;
+        let mut out = 0;
+        for _ in 0..digits {
+            match self.peek() {
+                Some(c @ b'0'..=b'9') => out = out * 10 + (c - b'0') as u32,
+                _ => return Err(self.at),
+            }
+            self.at += 1;
+        }
+        match out >= min && out <= max {
+            true => Ok(out),
+            false => Err(start),
+        }
+    }
+
+    fn date(&mut self) -> Result<(Date, bool), usize> {
+        let year = self.number(4, 0, 9999)? as u16;
+        let extended = self.eat(b'-');
+        let month = self.number(2, 1, 12)? as u8;
+        if extended {
+            self.expect(b'-')?;
+        }
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days = match month {
+            2 if leap => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        };
+        let day = self.number(2, 1, days)? as u8;
+        Ok((Date { year, month, day }, extended))
+    }
+
+    fn time(&mut self, extended: bool) -> Result<(u8, u8, u8, u32), usize> {
+        let hour = self.number(2, 0, 23)? as u8;
+        if extended {
+            self.expect(b':')?;
+        }
+        let minute = self.number(2, 0, 59)? as u8;
+        if extended {
+            self.expect(b':')?;
+        }
+        let second = self.number(2, 0, 60)? as u8;
+        let mut nanosecond = 0;
+        if self.eat(b'.') || self.eat(b',') {
+            let start = self.at;
+            while let Some(c @ b'0'..=b'9') = self.peek() {
+                if self.at - start < 9 {
+                    nanosecond = nanosecond * 10 + (c - b'0') as u32;
+                }
+                self.at += 1;
+            }
+            match self.at - start {
+                0 => return Err(self.at),
+                digits => nanosecond *= 10u32.pow(9 - digits.min(9) as u32),
+            }
+        }
+        Ok((hour, minute, second, nanosecond))
+    }
+
+    fn offset(&mut self, extended: bool) -> Result<Option<i16>, usize> {
+        let sign = match self.peek() {
+            Some(b'Z' | b'z') => {
+                self.at += 1;
+                return Ok(Some(0));
+            }
+            Some(b'+') => 1,
+            Some(b'-') => -1,
+            _ => return Ok(None),
+        };
+        self.at += 1;
+        let hours = self.number(2, 0, 23)? as i16;
+        let minutes = match self.peek() {
+            Some(b':') if extended => {
+                self.at += 1;
+                self.number(2, 0, 59)? as i16
+            }
+            Some(b'0'..=b'9') if !extended => self.number(2, 0, 59)? as i16,
+            _ => 0,
+        };
+        Ok(Some(sign * (hours * 60 + minutes)))
+    }
+}
+
+fn fail<'a, Output, Error: From<TimestampError<'a>>>(
+    input: &'a [u8],
+    offset: usize,
+) -> Step<'a, Output, Error> {
+    (input, Err(TimestampError { at: input, offset }.into()))
+}
+
+/// Parse an ISO 8601 date, like `2022-04-27` or `20220427`.
+pub fn iso_date<'a, Error: From<TimestampError<'a>>>(input: &'a [u8]) -> Step<'a, Date, Error> {
+    let mut fields = Fields { text: input, at: 0 };
+    match fields.date() {
+        Ok((date, _)) => (&input[fields.at..], Ok(date)),
+        Err(offset) => fail(input, offset),
+    }
+}
+
+/// Parse an ISO 8601 or RFC 3339 timestamp, like `2022-04-27T12:30:00.5+02:00` or
+/// `20220427T123000Z`.
+pub fn iso_timestamp<'a, Error: From<TimestampError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, Timestamp, Error> {
+    let mut fields = Fields { text: input, at: 0 };
+    let result = fields.date().and_then(|(date, extended)| {
+        match fields.peek() {
+            Some(b'T' | b't') => fields.at += 1,
+            Some(b' ') if extended => fields.at += 1,
+            _ => return Err(fields.at),
+        }
+        let (hour, minute, second, nanosecond) = fields.time(extended)?;
+        let offset = fields.offset(extended)?;
+        Ok(Timestamp {
+            date,
+            hour,
+            minute,
+            second,
+            nanosecond,
+            offset,
+        })
+    });
+    match result {
+        Ok(x) => (&input[fields.at..], Ok(x)),
+        Err(offset) => fail(input, offset),
+    }
+}
+
+std_error! {
+    TimestampError<'a>;
+}
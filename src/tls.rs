@@ -0,0 +1,233 @@
+//! TLS record and handshake parsers, enough to classify traffic and find the server name.
+
+use crate::{take, InputDebug, Step, TakeError};
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct TlsError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for TlsError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("TlsError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for TlsError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid TLS data")
+    }
+}
+
+impl<'a> From<TakeError<'a>> for TlsError<'a> {
+    fn from(x: TakeError<'a>) -> Self {
+        Self(x.0)
+    }
+}
+
+pub const CHANGE_CIPHER_SPEC: u8 = 20;
+pub const ALERT: u8 = 21;
+pub const HANDSHAKE: u8 = 22;
+pub const APPLICATION_DATA: u8 = 23;
+
+pub const CLIENT_HELLO: u8 = 1;
+pub const SERVER_HELLO: u8 = 2;
+
+pub const SERVER_NAME: u16 = 0;
+
+/// Records may be up to 2^14 bytes, plus 2048 bytes of expansion when encrypted.
+const MAX_RECORD_LEN: u16 = (1 << 14) + 2048;
+
+fn uint8<'a>(input: &'a [u8]) -> Step<'a, u8, TlsError<'a>> {
+    match input.split_first() {
+        Some((&b, rest)) => (rest, Ok(b)),
+        None => (input, Err(TlsError(input))),
+    }
+}
+
+fn uint16<'a>(input: &'a [u8]) -> Step<'a, u16, TlsError<'a>> {
+    let (rest, x) = step!(input, take::<TlsError>(2)(input));
+    (rest, Ok(u16::from_be_bytes([x[0], x[1]])))
+}
+
+fn uint24<'a>(input: &'a [u8]) -> Step<'a, u32, TlsError<'a>> {
+    let (rest, x) = step!(input, take::<TlsError>(3)(input));
+    (rest, Ok(u32::from_be_bytes([0, x[0], x[1], x[2]])))
+}
+
+/// Take bytes prefixed with their 8-bit length.
+fn vec8<'a>(input: &'a [u8]) -> Step<'a, &'a [u8], TlsError<'a>> {
+    let (rest, len) = step!(input, uint8(input));
+    let (rest, x) = step!(input, take::<TlsError>(len as usize)(rest));
+    (rest, Ok(x))
+}
+
+/// Take bytes prefixed with their 16-bit length.
+fn vec16<'a>(input: &'a [u8]) -> Step<'a, &'a [u8], TlsError<'a>> {
+    let (rest, len) = step!(input, uint16(input));
+    let (rest, x) = step!(input, take::<TlsError>(len as usize)(rest));
+    (rest, Ok(x))
+}
+
+/// The header of a record.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecordHeader {
+    /// The content type, like [`HANDSHAKE`]
+    pub content_type: u8,
+    /// The legacy record version, like `0x0303`
+    pub version: u16,
+    /// Length of the fragment after the header
+    pub len: u16,
+}
+
+/// Parse the header of a record, rejecting unknown content types and oversized records.
+pub fn record_header<'a, Error: From<TlsError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, RecordHeader, Error> {
+    let (rest, content_type) = step!(input, uint8(input));
+    let (rest, version) = step!(input, uint16(rest));
+    let (rest, len) = step!(input, uint16(rest));
+    let valid = (CHANGE_CIPHER_SPEC..=APPLICATION_DATA).contains(&content_type)
+        && version >> 8 == 3
+        && len <= MAX_RECORD_LEN;
+    match valid {
+        true => {
+            let header = RecordHeader {
+                content_type,
+                version,
+                len,
+            };
+            (rest, Ok(header))
+        }
+        false => (input, Err(TlsError(input).into())),
+    }
+}
+
+/// Parse a record, outputting its header and fragment.
+pub fn record<'a, Error: From<TlsError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, (RecordHeader, &'a [u8]), Error> {
+    let (rest, header) = step!(input, record_header::<TlsError>(input));
+    let (rest, fragment) = step!(input, take::<TlsError>(header.len as usize)(rest));
+    (rest, Ok((header, fragment)))
+}
+
+/// The header of a handshake message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HandshakeHeader {
+    /// The message type, like [`CLIENT_HELLO`]
+    pub msg_type: u8,
+    /// Length of the message after the header
+    pub len: u32,
+}
+
+/// Parse the header of a handshake message.
+pub fn handshake_header<'a, Error: From<TlsError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, HandshakeHeader, Error> {
+    let (rest, msg_type) = step!(input, uint8(input));
+    let (rest, len) = step!(input, uint24(rest));
+    (rest, Ok(HandshakeHeader { msg_type, len }))
+}
+
+/// An extension of a hello message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Extension<'a> {
+    /// The extension type, like [`SERVER_NAME`]
+    pub kind: u16,
+    pub data: &'a [u8],
+}
+
+/// Parse an extension.
+pub fn extension<'a, Error: From<TlsError<'a>>>(input: &'a [u8]) -> Step<'a, Extension<'a>, Error> {
+    let (rest, kind) = step!(input, uint16(input));
+    let (rest, data) = step!(input, vec16(rest));
+    (rest, Ok(Extension { kind, data }))
+}
+
+/// The extensions of a hello message, which can be iterated without allocating.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Extensions<'a>(
+    /// The encoded extensions, without their length
+    pub &'a [u8],
+);
+
+impl<'a> Iterator for Extensions<'a> {
+    type Item = Extension<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // The extensions were validated when they were parsed.
+        let (rest, x) = extension::<TlsError>(self.0);
+        self.0 = rest;
+        x.ok()
+    }
+}
+
+/// The body of a ClientHello message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientHello<'a> {
+    /// The legacy version, like `0x0303`
+    pub version: u16,
+    pub random: &'a [u8],
+    pub session_id: &'a [u8],
+    /// The cipher suites, two bytes each
+    pub cipher_suites: &'a [u8],
+    pub compression_methods: &'a [u8],
+    pub extensions: Extensions<'a>,
+}
+
+impl<'a> ClientHello<'a> {
+    /// The host name from the server name extension, if any.
+    pub fn server_name(&self) -> Option<&'a [u8]> {
+        let extension = self
+            .extensions
+            .into_iter()
+            .find(|x| x.kind == SERVER_NAME)?;
+        let mut names = vec16(extension.data).1.ok()?;
+        while !names.is_empty() {
+            let (rest, name_type) = uint8(names);
+            let (rest, name) = vec16(rest);
+            match (name_type.ok()?, name.ok()?) {
+                (0, name) => return Some(name),
+                _ => names = rest,
+            }
+        }
+        None
+    }
+}
+
+/// Parse the body of a ClientHello message, after its handshake header.
+pub fn client_hello<'a, Error: From<TlsError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, ClientHello<'a>, Error> {
+    let (rest, version) = step!(input, uint16(input));
+    let (rest, random) = step!(input, take::<TlsError>(32)(rest));
+    let (rest, session_id) = step!(input, vec8(rest));
+    let (rest, cipher_suites) = step!(input, vec16(rest));
+    let (rest, compression_methods) = step!(input, vec8(rest));
+    let (rest, mut extensions) = match rest.is_empty() {
+        true => (rest, &rest[..0]),
+        false => step!(input, vec16(rest)),
+    };
+    let all = extensions;
+    while !extensions.is_empty() {
+        extensions = step!(input, extension::<TlsError>(extensions)).0;
+    }
+    let hello = ClientHello {
+        version,
+        random,
+        session_id,
+        cipher_suites,
+        compression_methods,
+        extensions: Extensions(all),
+    };
+    (rest, Ok(hello))
+}
+
+std_error! {
+    TlsError<'a>;
+}
@@ -0,0 +1,100 @@
+use crate::{InputDebug, Step};
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct TupleError<'a, ChildError> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// Position in the tuple of the parser that failed
+    pub index: usize,
+    /// The child parser's error
+    pub child_error: ChildError,
+}
+
+impl<'a, ChildError: Debug> Debug for TupleError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("TupleError")
+            .field("at", &InputDebug(self.at))
+            .field("index", &self.index)
+            .field("child_error", &self.child_error)
+            .finish()
+    }
+}
+
+impl<'a, ChildError: Display> Display for TupleError<'a, ChildError> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "parser {}: {}", self.index, self.child_error)
+    }
+}
+
+/// A tuple of parsers that run in sequence. Implemented for tuples of 2 to 12 parsers.
+pub trait Tuple<'a, Output, Error> {
+    fn parse_tuple(&self, input: &'a [u8]) -> Step<'a, Output, Error>;
+}
+
+macro_rules! tuple_impl {
+    ($($parser:ident $output:ident $error:ident $index:tt),+) => {
+        impl<'a, Error, $($parser, $output, $error),+> Tuple<'a, ($($output,)+), Error>
+            for ($($parser,)+)
+        where
+            $($parser: Fn(&'a [u8]) -> Step<'a, $output, $error>,)+
+            $(Error: From<TupleError<'a, $error>>,)+
+        {
+            #[allow(non_snake_case)]
+            fn parse_tuple(&self, input: &'a [u8]) -> Step<'a, ($($output,)+), Error> {
+                let at = input;
+                $(
+                    let (at, $output) = match (self.$index)(at) {
+                        (rest, Ok(x)) => (rest, x),
+                        (_, Err(child_error)) => {
+                            let error = TupleError {
+                                at,
+                                index: $index,
+                                child_error,
+                            };
+                            return (input, Err(error.into()));
+                        }
+                    };
+                )+
+                (at, Ok(($($output,)+)))
+            }
+        }
+    };
+}
+
+tuple_impl!(A OA EA 0, B OB EB 1);
+tuple_impl!(A OA EA 0, B OB EB 1, C OC EC 2);
+tuple_impl!(A OA EA 0, B OB EB 1, C OC EC 2, D OD ED 3);
+tuple_impl!(A OA EA 0, B OB EB 1, C OC EC 2, D OD ED 3, E OE EE 4);
+tuple_impl!(A OA EA 0, B OB EB 1, C OC EC 2, D OD ED 3, E OE EE 4, F OF EF 5);
+tuple_impl!(A OA EA 0, B OB EB 1, C OC EC 2, D OD ED 3, E OE EE 4, F OF EF 5, G OG EG 6);
+tuple_impl!(
+    A OA EA 0, B OB EB 1, C OC EC 2, D OD ED 3, E OE EE 4, F OF EF 5, G OG EG 6, H OH EH 7
+);
+tuple_impl!(
+    A OA EA 0, B OB EB 1, C OC EC 2, D OD ED 3, E OE EE 4, F OF EF 5, G OG EG 6, H OH EH 7,
+    I OI EI 8
+);
+tuple_impl!(
+    A OA EA 0, B OB EB 1, C OC EC 2, D OD ED 3, E OE EE 4, F OF EF 5, G OG EG 6, H OH EH 7,
+    I OI EI 8, J OJ EJ 9
+);
+tuple_impl!(
+    A OA EA 0, B OB EB 1, C OC EC 2, D OD ED 3, E OE EE 4, F OF EF 5, G OG EG 6, H OH EH 7,
+    I OI EI 8, J OJ EJ 9, K OK EK 10
+);
+tuple_impl!(
+    A OA EA 0, B OB EB 1, C OC EC 2, D OD ED 3, E OE EE 4, F OF EF 5, G OG EG 6, H OH EH 7,
+    I OI EI 8, J OJ EJ 9, K OK EK 10, L OL EL 11
+);
+
+/// Run a tuple of parsers in sequence, outputting a flat tuple of their outputs.
+pub fn tuple<'a, Output, Error>(
+    parsers: impl Tuple<'a, Output, Error>,
+) -> impl Fn(&'a [u8]) -> Step<'a, Output, Error> {
+    move |input| parsers.parse_tuple(input)
+}
+
+std_error! {
+    TupleError<'a, ChildError>;
+}
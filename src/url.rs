@@ -0,0 +1,214 @@
+#[cfg(feature = "alloc")]
+use crate::hex::digit;
+use crate::{InputDebug, Step};
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct PercentError<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// Offset of the invalid escape from where the error happened
+    pub offset: usize,
+}
+
+impl<'a> Debug for PercentError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("PercentError")
+            .field("at", &InputDebug(self.at))
+            .field("offset", &self.offset)
+            .finish()
+    }
+}
+
+impl<'a> Display for PercentError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid percent escape at offset {}", self.offset)
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct UrlError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for UrlError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("UrlError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for UrlError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid URL")
+    }
+}
+
+/// Take the rest of the input, decoding percent escapes. The output is only copied when there is
+/// an escape to decode.
+#[cfg(feature = "alloc")]
+pub fn percent_decode<'a, Error: From<PercentError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, Cow<'a, [u8]>, Error> {
+    let end = &input[input.len()..];
+    let first = match input.iter().position(|&b| b == b'%') {
+        Some(x) => x,
+        None => return (end, Ok(Cow::Borrowed(input))),
+    };
+    let mut out = Vec::with_capacity(input.len());
+    out.extend_from_slice(&input[..first]);
+    let mut i = first;
+    while i < input.len() {
+        if input[i] != b'%' {
+            out.push(input[i]);
+            i += 1;
+            continue;
+        }
+        let hi = input.get(i + 1).copied().and_then(digit);
+        let lo = input.get(i + 2).copied().and_then(digit);
+        match (hi, lo) {
+            (Some(hi), Some(lo)) => out.push(hi << 4 | lo),
+            _ => {
+                return (
+                    input,
+                    Err(PercentError {
+                        at: input,
+                        offset: i,
+                    }
+                    .into()),
+                )
+            }
+        }
+        i += 3;
+    }
+    (end, Ok(Cow::Owned(out)))
+}
+
+/// Whitespace and control characters end a URL.
+fn is_end(b: u8) -> bool {
+    b <= b' ' || b == 0x7f
+}
+
+/// Split off bytes until one matches `stop` or ends the URL.
+fn split_until(input: &[u8], stop: impl Fn(u8) -> bool) -> (&[u8], &[u8]) {
+    let len = input
+        .iter()
+        .position(|&b| stop(b) || is_end(b))
+        .unwrap_or(input.len());
+    input.split_at(len)
+}
+
+/// Parse a URL scheme and the `:` after it, outputting the scheme.
+pub fn url_scheme<'a, Error: From<UrlError<'a>>>(input: &'a [u8]) -> Step<'a, &'a [u8], Error> {
+    let (scheme, rest) = split_until(input, |b| {
+        !(b.is_ascii_alphanumeric() || b == b'+' || b == b'-' || b == b'.')
+    });
+    match (scheme.first(), rest.first()) {
+        (Some(b), Some(b':')) if b.is_ascii_alphabetic() => (&rest[1..], Ok(scheme)),
+        _ => (input, Err(UrlError(input).into())),
+    }
+}
+
+/// Parse a URL authority after `//`, outputting the authority.
+pub fn url_authority<'a, Error: From<UrlError<'a>>>(input: &'a [u8]) -> Step<'a, &'a [u8], Error> {
+    match input.strip_prefix(b"//") {
+        Some(x) => {
+            let (authority, rest) = split_until(x, |b| b == b'/' || b == b'?' || b == b'#');
+            (rest, Ok(authority))
+        }
+        None => (input, Err(UrlError(input).into())),
+    }
+}
+
+/// Parse a URL path, which may be empty.
+pub fn url_path<'a, Error: From<UrlError<'a>>>(input: &'a [u8]) -> Step<'a, &'a [u8], Error> {
+    let (path, rest) = split_until(input, |b| b == b'?' || b == b'#');
+    (rest, Ok(path))
+}
+
+/// Parse a URL query after `?`, outputting the query.
+pub fn url_query<'a, Error: From<UrlError<'a>>>(input: &'a [u8]) -> Step<'a, &'a [u8], Error> {
+    match input.strip_prefix(b"?") {
+        Some(x) => {
+            let (query, rest) = split_until(x, |b| b == b'#');
+            (rest, Ok(query))
+        }
+        None => (input, Err(UrlError(input).into())),
+    }
+}
+
+/// Parse a URL fragment after `#`, outputting the fragment.
+pub fn url_fragment<'a, Error: From<UrlError<'a>>>(input: &'a [u8]) -> Step<'a, &'a [u8], Error> {
+    match input.strip_prefix(b"#") {
+        Some(x) => {
+            let (fragment, rest) = split_until(x, |_| false);
+            (rest, Ok(fragment))
+        }
+        None => (input, Err(UrlError(input).into())),
+    }
+}
+
+/// The components of a URL. Each component is still percent encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Url<'a> {
+    /// The scheme, without the `:`
+    pub scheme: Option<&'a [u8]>,
+    /// The authority, without the `//`
+    pub authority: Option<&'a [u8]>,
+    /// The path, which may be empty
+    pub path: &'a [u8],
+    /// The query, without the `?`
+    pub query: Option<&'a [u8]>,
+    /// The fragment, without the `#`
+    pub fragment: Option<&'a [u8]>,
+}
+
+/// Parse a URL or relative reference into its components, stopping at whitespace.
+pub fn url<'a, Error: From<UrlError<'a>>>(input: &'a [u8]) -> Step<'a, Url<'a>, Error> {
+    let (rest, scheme) = url_scheme::<UrlError>(input);
+    let (rest, authority) = url_authority::<UrlError>(rest);
+    let (rest, path) = url_path::<UrlError>(rest);
+    let (rest, query) = url_query::<UrlError>(rest);
+    let (rest, fragment) = url_fragment::<UrlError>(rest);
+    let url = Url {
+        scheme: scheme.ok(),
+        authority: authority.ok(),
+        path: path.ok().unwrap_or_default(),
+        query: query.ok(),
+        fragment: fragment.ok(),
+    };
+    match rest.len() < input.len() {
+        true => (rest, Ok(url)),
+        false => (input, Err(UrlError(input).into())),
+    }
+}
+
+std_error! {
+    PercentError<'a>;
+    UrlError<'a>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn url_components() {
+        let (rest, x) = url::<UrlError>(b"https://example.com/a/b?q=1#top");
+        let expected = Url {
+            scheme: Some(b"https"),
+            authority: Some(b"example.com"),
+            path: b"/a/b",
+            query: Some(b"q=1"),
+            fragment: Some(b"top"),
+        };
+        assert_eq!(rest, &[]);
+        assert_eq!(x.unwrap(), expected);
+    }
+}
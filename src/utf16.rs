@@ -0,0 +1,200 @@
+use crate::{take, CStrError, Endian, InputDebug, Step, TakeError};
+use core::fmt::{self, Debug, Display, Formatter, Write};
+
+/// Validated UTF-16 text from [`utf16l`], [`utf16b`], [`cstr_utf16l`], or [`cstr_utf16b`].
+/// Decoding it doesn't allocate, and with the `alloc` feature `to_string` decodes it into a
+/// `String`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Utf16<'a> {
+    bytes: &'a [u8],
+    endian: Endian,
+}
+
+impl<'a> Utf16<'a> {
+    /// The encoded bytes.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    /// The byte order of the code units.
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    /// Iterate over the code units.
+    pub fn units(&self) -> impl Iterator<Item = u16> + 'a {
+        let endian = self.endian;
+        self.bytes.chunks_exact(2).map(move |x| endian.read(x))
+    }
+
+    /// Iterate over the characters.
+    pub fn chars(&self) -> impl Iterator<Item = char> + 'a {
+        // Already validated, so there's nothing to replace
+        char::decode_utf16(self.units()).map(|x| x.unwrap_or(char::REPLACEMENT_CHARACTER))
+    }
+}
+
+impl<'a> Display for Utf16<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.chars().try_for_each(|c| fmt.write_char(c))
+    }
+}
+
+impl<'a> Debug for Utf16<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.write_char('"')?;
+        for c in self.chars().flat_map(char::escape_debug) {
+            fmt.write_char(c)?;
+        }
+        fmt.write_char('"')
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Utf16Error<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// Offset from `at` of the unpaired surrogate
+    pub valid_up_to: usize,
+}
+
+impl<'a> Debug for Utf16Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Utf16Error")
+            .field("at", &InputDebug(self.at))
+            .field("valid_up_to", &self.valid_up_to)
+            .finish()
+    }
+}
+
+impl<'a> Display for Utf16Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "unpaired surrogate after {} bytes", self.valid_up_to)
+    }
+}
+
+/// Validate UTF-16, with the error at the start of the text.
+fn to_utf16(bytes: &[u8], endian: Endian) -> Result<Utf16<'_>, Utf16Error<'_>> {
+    let text = Utf16 { bytes, endian };
+    let mut units = 0;
+    for x in char::decode_utf16(text.units()) {
+        match x {
+            Ok(c) => units += c.len_utf16(),
+            Err(_) => {
+                return Err(Utf16Error {
+                    at: bytes,
+                    valid_up_to: units * 2,
+                })
+            }
+        }
+    }
+    Ok(text)
+}
+
+/// Take N code units of UTF-16 text.
+fn utf16_count<'a, Error>(
+    endian: Endian,
+    count: usize,
+    input: &'a [u8],
+) -> Step<'a, Utf16<'a>, Error>
+where
+    Error: From<TakeError<'a>> + From<Utf16Error<'a>>,
+{
+    let (rest, out) = step!(input, take::<TakeError>(count.saturating_mul(2))(input));
+    match to_utf16(out, endian) {
+        Ok(x) => (rest, Ok(x)),
+        Err(e) => (input, Err(e.into())),
+    }
+}
+
+/// Take UTF-16 text up to a null terminator within `max_len` code units, consuming it.
+fn utf16_cstr<'a, Error>(
+    endian: Endian,
+    max_len: usize,
+    input: &'a [u8],
+) -> Step<'a, Utf16<'a>, Error>
+where
+    Error: From<CStrError<'a>> + From<Utf16Error<'a>>,
+{
+    let max_bytes = max_len.saturating_mul(2);
+    let window = &input[..input.len().min(max_bytes)];
+    let len = match window.chunks_exact(2).position(|x| x == [0, 0]) {
+        Some(i) => i * 2,
+        None => return (input, Err(CStrError { at: input, max_len }.into())),
+    };
+    match to_utf16(&input[..len], endian) {
+        Ok(x) => (&input[len + 2..], Ok(x)),
+        Err(e) => (input, Err(e.into())),
+    }
+}
+
+/// Take N code units of UTF-16 little-endian text. Use [`and_then`](crate::and_then) for a
+/// length prefix, like `and_then(u16l, |len| utf16l(len.into()))`.
+pub fn utf16l<'a, Error: From<TakeError<'a>> + From<Utf16Error<'a>>>(
+    count: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, Utf16<'a>, Error> {
+    move |input| utf16_count(Endian::Little, count, input)
+}
+
+/// Take N code units of UTF-16 big-endian text. See [`utf16l`].
+pub fn utf16b<'a, Error: From<TakeError<'a>> + From<Utf16Error<'a>>>(
+    count: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, Utf16<'a>, Error> {
+    move |input| utf16_count(Endian::Big, count, input)
+}
+
+/// Take UTF-16 little-endian text up to a null terminator, consuming the terminator. Fails if
+/// there's no terminator in the first `max_len` code units.
+pub fn cstr_utf16l<'a, Error: From<CStrError<'a>> + From<Utf16Error<'a>>>(
+    max_len: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, Utf16<'a>, Error> {
+    move |input| utf16_cstr(Endian::Little, max_len, input)
+}
+
+/// Take UTF-16 big-endian text up to a null terminator, consuming the terminator. See
+/// [`cstr_utf16l`].
+pub fn cstr_utf16b<'a, Error: From<CStrError<'a>> + From<Utf16Error<'a>>>(
+    max_len: usize,
+) -> impl Fn(&'a [u8]) -> Step<'a, Utf16<'a>, Error> {
+    move |input| utf16_cstr(Endian::Big, max_len, input)
+}
+
+std_error! {
+    Utf16Error<'a>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseError;
+
+    #[derive(Debug)]
+    enum Error<'a> {
+        CStr(CStrError<'a>),
+        Utf16,
+    }
+
+    impl<'a> From<CStrError<'a>> for Error<'a> {
+        fn from(x: CStrError<'a>) -> Self {
+            Self::CStr(x)
+        }
+    }
+
+    impl<'a> From<Utf16Error<'a>> for Error<'a> {
+        fn from(_: Utf16Error<'a>) -> Self {
+            Self::Utf16
+        }
+    }
+
+    #[test]
+    fn cstr_max_len_in_code_units() {
+        let input = [b'a', 0, b'b', 0, b'c', 0, 0, 0];
+        match cstr_utf16l::<Error>(2)(&input).1 {
+            Err(Error::CStr(e)) => assert_eq!(e.max_len, 2),
+            x => panic!("expected CStrError, got {:?}", x),
+        }
+        let (rest, x) = cstr_utf16l::<ParseError>(4)(&input);
+        assert_eq!(rest, &[]);
+        assert!(x.unwrap().units().eq([0x61, 0x62, 0x63]));
+    }
+}
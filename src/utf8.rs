@@ -0,0 +1,131 @@
+use crate::InputDebug;
+use core::fmt::{self, Debug, Display, Formatter};
+use core::str;
+
+#[derive(Clone, PartialEq)]
+pub struct Utf8Error<'a> {
+    /// Where the error happened
+    pub at: &'a [u8],
+    /// Offset from `at` where the invalid UTF-8 starts
+    pub valid_up_to: usize,
+}
+
+impl<'a> Debug for Utf8Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("Utf8Error")
+            .field("at", &InputDebug(self.at))
+            .field("valid_up_to", &self.valid_up_to)
+            .finish()
+    }
+}
+
+impl<'a> Display for Utf8Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid UTF-8 after {} bytes", self.valid_up_to)
+    }
+}
+
+/// Validate UTF-8, with the error at the start of the text.
+pub(crate) fn to_str(bytes: &[u8]) -> Result<&str, Utf8Error<'_>> {
+    str::from_utf8(bytes).map_err(|e| Utf8Error {
+        at: bytes,
+        valid_up_to: e.valid_up_to(),
+    })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Utf8StreamError {
+    /// Offset from the start of the stream where the invalid UTF-8 starts
+    pub offset: usize,
+}
+
+impl Display for Utf8StreamError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid UTF-8 at offset {}", self.offset)
+    }
+}
+
+/// Validates UTF-8 that arrives in chunks, allowing code points to be split between chunks.
+#[derive(Clone, Default)]
+pub struct Utf8Validator {
+    /// Bytes of a code point that was split at the end of the last chunk
+    pending: [u8; 4],
+    pending_len: usize,
+    /// Offset from the start of the stream to the start of the next chunk
+    offset: usize,
+}
+
+/// Length of a code point from its first byte, or `None` if it can't start a code point.
+fn width(first: u8) -> Option<usize> {
+    match first {
+        0x00..=0x7f => Some(1),
+        0xc2..=0xdf => Some(2),
+        0xe0..=0xef => Some(3),
+        0xf0..=0xf4 => Some(4),
+        _ => None,
+    }
+}
+
+impl Utf8Validator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validate the next chunk of the stream.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<(), Utf8StreamError> {
+        let mut rest = chunk;
+        if self.pending_len != 0 {
+            let start = self.offset - self.pending_len;
+            let error = Utf8StreamError { offset: start };
+            let width = width(self.pending[0]).ok_or(error)?;
+            let needed = (width - self.pending_len).min(rest.len());
+            self.pending[self.pending_len..self.pending_len + needed]
+                .copy_from_slice(&rest[..needed]);
+            self.pending_len += needed;
+            rest = &rest[needed..];
+            let error = Utf8StreamError { offset: start };
+            match str::from_utf8(&self.pending[..self.pending_len]) {
+                Ok(_) => self.pending_len = 0,
+                Err(e) if e.error_len().is_none() => {
+                    self.offset += chunk.len();
+                    return Ok(());
+                }
+                Err(_) => return Err(error),
+            }
+        }
+        let rest_offset = self.offset + (chunk.len() - rest.len());
+        self.offset += chunk.len();
+        match str::from_utf8(rest) {
+            Ok(_) => Ok(()),
+            Err(e) if e.error_len().is_none() => {
+                let tail = &rest[e.valid_up_to()..];
+                self.pending[..tail.len()].copy_from_slice(tail);
+                self.pending_len = tail.len();
+                Ok(())
+            }
+            Err(e) => Err(Utf8StreamError {
+                offset: rest_offset + e.valid_up_to(),
+            }),
+        }
+    }
+
+    /// How many bytes of a split code point are waiting for the next chunk.
+    pub fn pending(&self) -> usize {
+        self.pending_len
+    }
+
+    /// Ensure the stream didn't end partway through a code point.
+    pub fn finish(&self) -> Result<(), Utf8StreamError> {
+        match self.pending_len {
+            0 => Ok(()),
+            _ => Err(Utf8StreamError {
+                offset: self.offset - self.pending_len,
+            }),
+        }
+    }
+}
+
+std_error! {
+    Utf8StreamError;
+    Utf8Error<'a>;
+}
@@ -0,0 +1,265 @@
+use crate::{InputDebug, Step};
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct QuicVarintError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for QuicVarintError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("QuicVarintError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for QuicVarintError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "incomplete QUIC variable-length integer")
+    }
+}
+
+/// Parse a QUIC variable-length integer, outputting the value and how many bytes encoded it. The
+/// two high bits of the first byte give the length, so the value is at most 2^62 - 1.
+pub fn quic_varint<'a, Error: From<QuicVarintError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, (u64, usize), Error> {
+    let len = match input.first() {
+        Some(first) => 1 << (first >> 6),
+        None => return (input, Err(QuicVarintError(input).into())),
+    };
+    match input.get(..len) {
+        Some(bytes) => {
+            let value = bytes[1..]
+                .iter()
+                .fold((bytes[0] & 0x3f) as u64, |n, &b| n << 8 | b as u64);
+            (&input[len..], Ok((value, len)))
+        }
+        None => (input, Err(QuicVarintError(input).into())),
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct CompactSizeError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for CompactSizeError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("CompactSizeError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for CompactSizeError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "incomplete CompactSize integer")
+    }
+}
+
+/// Parse a Bitcoin CompactSize integer. Values below `0xfd` are a single byte, and larger ones
+/// follow a `0xfd`, `0xfe`, or `0xff` prefix as 2, 4, or 8 little endian bytes. Encodings that
+/// aren't as short as possible are rejected.
+pub fn compact_size<'a, Error: From<CompactSizeError<'a>>>(
+    input: &'a [u8],
+) -> Step<'a, u64, Error> {
+    let (len, min) = match input.first() {
+        Some(0xfd) => (2, 0xfd),
+        Some(0xfe) => (4, 0x1_0000),
+        Some(0xff) => (8, 0x1_0000_0000),
+        Some(&b) => return (&input[1..], Ok(b as u64)),
+        None => return (input, Err(CompactSizeError(input).into())),
+    };
+    match input.get(1..1 + len) {
+        Some(bytes) => {
+            let value = bytes.iter().rev().fold(0, |n, &b| n << 8 | b as u64);
+            if value < min {
+                return (input, Err(CompactSizeError(input).into()));
+            }
+            (&input[1 + len..], Ok(value))
+        }
+        None => (input, Err(CompactSizeError(input).into())),
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct Leb128Error<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for Leb128Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("Leb128Error")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for Leb128Error<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "incomplete or overflowing LEB128 integer")
+    }
+}
+
+/// Parse an unsigned LEB128 integer, as used by DWARF and WebAssembly. Each byte holds 7 bits of
+/// the value, least significant first, with the high bit set on all but the last. Values that
+/// don't fit in a `u64` are rejected.
+pub fn uleb128<'a, Error: From<Leb128Error<'a>>>(input: &'a [u8]) -> Step<'a, u64, Error> {
+    let mut value = 0;
+    for (i, &b) in input.iter().enumerate() {
+        let shift = 7 * i as u32;
+        let bits = u64::from(b & 0x7f);
+        // The 10th byte only has room for the top bit
+        if shift >= 64 || (shift == 63 && bits > 1) {
+            break;
+        }
+        value |= bits << shift;
+        if b & 0x80 == 0 {
+            return (&input[i + 1..], Ok(value));
+        }
+    }
+    (input, Err(Leb128Error(input).into()))
+}
+
+/// Parse a signed LEB128 integer, where the highest bit of the last 7 is the sign. Values that
+/// don't fit in an `i64` are rejected.
+pub fn sleb128<'a, Error: From<Leb128Error<'a>>>(input: &'a [u8]) -> Step<'a, i64, Error> {
+    let mut value = 0;
+    for (i, &b) in input.iter().enumerate() {
+        let shift = 7 * i as u32;
+        let bits = i64::from(b & 0x7f);
+        // The 10th byte only has room for the sign, so its bits must all match it
+        if shift >= 64 || (shift == 63 && bits != 0 && bits != 0x7f) {
+            break;
+        }
+        value |= bits << shift;
+        if b & 0x80 == 0 {
+            if shift + 7 < 64 && b & 0x40 != 0 {
+                value |= -1 << (shift + 7);
+            }
+            return (&input[i + 1..], Ok(value));
+        }
+    }
+    (input, Err(Leb128Error(input).into()))
+}
+
+#[derive(Clone, PartialEq)]
+pub struct VarintError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for VarintError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("VarintError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for VarintError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "incomplete varint")
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct VarintOverlongError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for VarintOverlongError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("VarintOverlongError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for VarintOverlongError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "varint is longer than 10 bytes")
+    }
+}
+
+/// Parse a protobuf varint. Like [`uleb128`], but bits past the 64th are dropped, as protobuf
+/// does for negative `int32` values, and only encodings longer than 10 bytes are rejected.
+pub fn varint_u64<'a, Error>(input: &'a [u8]) -> Step<'a, u64, Error>
+where
+    Error: From<VarintError<'a>> + From<VarintOverlongError<'a>>,
+{
+    let mut value = 0;
+    for (i, &b) in input.iter().enumerate() {
+        if i == 10 {
+            return (input, Err(VarintOverlongError(input).into()));
+        }
+        // The 10th byte's bits past the 64th are shifted out
+        value |= u64::from(b & 0x7f) << (7 * i);
+        if b & 0x80 == 0 {
+            return (&input[i + 1..], Ok(value));
+        }
+    }
+    (input, Err(VarintError(input).into()))
+}
+
+/// Parse a protobuf `sint64`, a varint with the sign in the lowest bit so small negative values
+/// stay short.
+pub fn zigzag_i64<'a, Error>(input: &'a [u8]) -> Step<'a, i64, Error>
+where
+    Error: From<VarintError<'a>> + From<VarintOverlongError<'a>>,
+{
+    let (rest, x) = step!(input, varint_u64::<Error>(input));
+    (rest, Ok((x >> 1) as i64 ^ -((x & 1) as i64)))
+}
+
+#[derive(Clone, PartialEq)]
+pub struct VlqError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for VlqError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("VlqError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for VlqError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "incomplete or overflowing variable-length quantity")
+    }
+}
+
+/// Parse a variable-length quantity, as used by MIDI. Like [`uleb128`], but the 7-bit groups are
+/// most significant first. Encodings longer than 5 bytes, or values that don't fit in a `u32`,
+/// are rejected.
+pub fn vlq_u32<'a, Error: From<VlqError<'a>>>(input: &'a [u8]) -> Step<'a, u32, Error> {
+    let mut value: u32 = 0;
+    for (i, &b) in input.iter().enumerate().take(5) {
+        if value > u32::MAX >> 7 {
+            break;
+        }
+        value = value << 7 | u32::from(b & 0x7f);
+        if b & 0x80 == 0 {
+            return (&input[i + 1..], Ok(value));
+        }
+    }
+    (input, Err(VlqError(input).into()))
+}
+
+std_error! {
+    QuicVarintError<'a>;
+    CompactSizeError<'a>;
+    Leb128Error<'a>;
+    VarintError<'a>;
+    VarintOverlongError<'a>;
+    VlqError<'a>;
+}
@@ -0,0 +1,143 @@
+//! WebSocket frame parsers, following RFC 6455.
+
+use crate::{take, InputDebug, Step, TakeError};
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[derive(Clone, PartialEq)]
+pub struct WsError<'a>(
+    /// Where the error happened
+    pub &'a [u8],
+);
+
+impl<'a> Debug for WsError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        fmt.debug_tuple("WsError")
+            .field(&InputDebug(self.0))
+            .finish()
+    }
+}
+
+impl<'a> Display for WsError<'a> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "invalid WebSocket frame")
+    }
+}
+
+impl<'a> From<TakeError<'a>> for WsError<'a> {
+    fn from(x: TakeError<'a>) -> Self {
+        Self(x.0)
+    }
+}
+
+/// The header of a frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FrameHeader {
+    /// Whether this is the final frame of a message
+    pub fin: bool,
+    /// The three reserved bits, for use by extensions
+    pub rsv: u8,
+    pub opcode: u8,
+    /// The key the payload is masked with, if any
+    pub mask: Option<[u8; 4]>,
+    /// Length of the payload
+    pub len: u64,
+}
+
+pub const CONTINUATION: u8 = 0x0;
+pub const TEXT: u8 = 0x1;
+pub const BINARY: u8 = 0x2;
+pub const CLOSE: u8 = 0x8;
+pub const PING: u8 = 0x9;
+pub const PONG: u8 = 0xa;
+
+/// Parse a frame header, rejecting reserved opcodes, fragmented or oversized control frames, and
+/// lengths not in their shortest form.
+pub fn frame_header<'a, Error: From<WsError<'a>>>(input: &'a [u8]) -> Step<'a, FrameHeader, Error> {
+    let (rest, bytes) = take::<WsError>(2)(input);
+    let (first, second) = match bytes {
+        Ok(x) => (x[0], x[1]),
+        Err(e) => return (input, Err(e.into())),
+    };
+    let fin = first & 0x80 != 0;
+    let rsv = (first >> 4) & 0b111;
+    let opcode = first & 0x0f;
+    let masked = second & 0x80 != 0;
+    let len = second & 0x7f;
+    match opcode {
+        CONTINUATION | TEXT | BINARY => {}
+        CLOSE | PING | PONG if fin && len <= 125 => {}
+        _ => return (input, Err(WsError(input).into())),
+    }
+    let (rest, len) = match len {
+        126 => match take::<WsError>(2)(rest) {
+            (rest, Ok(x)) => (rest, u16::from_be_bytes([x[0], x[1]]) as u64),
+            (_, Err(e)) => return (input, Err(e.into())),
+        },
+        127 => match take::<WsError>(8)(rest) {
+            (rest, Ok(x)) => {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(x);
+                (rest, u64::from_be_bytes(bytes))
+            }
+            (_, Err(e)) => return (input, Err(e.into())),
+        },
+        len => (rest, len as u64),
+    };
+    let shortest = match second & 0x7f {
+        126 => len > 125,
+        127 => len > 0xffff && len >> 63 == 0,
+        _ => true,
+    };
+    if !shortest {
+        return (input, Err(WsError(input).into()));
+    }
+    let (rest, mask) = match masked {
+        true => match take::<WsError>(4)(rest) {
+            (rest, Ok(x)) => (rest, Some([x[0], x[1], x[2], x[3]])),
+            (_, Err(e)) => return (input, Err(e.into())),
+        },
+        false => (rest, None),
+    };
+    let header = FrameHeader {
+        fin,
+        rsv,
+        opcode,
+        mask,
+        len,
+    };
+    (rest, Ok(header))
+}
+
+/// Unmask a payload in place. `offset` is where `data` starts within the payload.
+pub fn unmask(key: [u8; 4], offset: usize, data: &mut [u8]) {
+    for (i, b) in data.iter_mut().enumerate() {
+        *b ^= key[(offset + i) % 4];
+    }
+}
+
+/// Take the payload of a frame, unmasking it if needed. The output is only copied when the
+/// payload is masked.
+#[cfg(feature = "alloc")]
+pub fn payload<'a, Error: From<WsError<'a>>>(
+    header: &FrameHeader,
+) -> impl Fn(&'a [u8]) -> Step<'a, Cow<'a, [u8]>, Error> {
+    let mask = header.mask;
+    let len = usize::try_from(header.len).unwrap_or(usize::MAX);
+    move |input| match take::<WsError>(len)(input) {
+        (rest, Ok(data)) => match mask {
+            Some(key) => {
+                let mut data = data.to_vec();
+                unmask(key, 0, &mut data);
+                (rest, Ok(Cow::Owned(data)))
+            }
+            None => (rest, Ok(Cow::Borrowed(data))),
+        },
+        (_, Err(e)) => (input, Err(e.into())),
+    }
+}
+
+std_error! {
+    WsError<'a>;
+}